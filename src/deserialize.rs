@@ -1,5 +1,7 @@
-use crate::error::ParseError;
-use crate::parser::Parser;
+use std::io::Read;
+
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parser::{ParseOptions, Parser};
 use crate::values::Values;
 /// Auto Trait for Deserializing JSON into predefined Structs.
 ///
@@ -51,13 +53,307 @@ pub trait Deserialize: TryFrom<Values, Error = ParseError> {
     fn deserialize_str(str: &str) -> Result<Self, ParseError> {
         Parser::new(str).parse().map(Self::try_from)?
     }
+    /// deserialize a &str containing JSON into the provided Struct
+    /// while using the provided [`ParseOptions`]
+    fn deserialize_str_with(str: &str, options: ParseOptions) -> Result<Self, ParseError> {
+        Parser::with_options(str, options)
+            .parse()
+            .map(Self::try_from)?
+    }
+    /// deserialize a &str containing multiple JSON values, separated by whitespace
+    /// or a single comma, into a [`Vec`] of the provided Struct
+    ///
+    /// ```
+    /// use wjp::Deserialize;
+    /// assert_eq!(i32::deserialize_many("1 2\n3"), Ok(vec![1, 2, 3]));
+    /// ```
+    fn deserialize_many(str: &str) -> Result<Vec<Self>, ParseError> {
+        Parser::new(str)
+            .parse_many()?
+            .into_iter()
+            .map(Self::try_from)
+            .collect()
+    }
+    /// deserialize a byte slice containing UTF-8 encoded JSON into the provided Struct.
+    /// Invalid UTF-8 inside of a String results in a [`ParseError`] of the kind [`InvalidUtf8`]
+    ///
+    /// [`InvalidUtf8`]: crate::ParseErrorKind::InvalidUtf8
+    fn deserialize_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        Parser::from_bytes(bytes, ParseOptions::new())
+            .parse()
+            .map(Self::try_from)?
+    }
+    /// read a single JSON message from the provided reader and deserialize it into the provided Struct.
+    /// The message needs to be prefixed with its length in bytes as a big endian [`u32`]
+    /// like it is written by [`Serialize::json_framed_to_writer`].
+    ///
+    /// The buffer only grows with the bytes that actually arrive, so a forged prefix doesn't
+    /// allocate up to 4 GiB upfront. Use [`Deserialize::from_framed_reader_with`] and
+    /// [`ParseOptions::with_max_length`] to limit the size of messages from untrusted peers
+    ///
+    /// [`Serialize::json_framed_to_writer`]: crate::Serialize::json_framed_to_writer
+    fn from_framed_reader<R: Read>(reader: &mut R) -> Result<Self, ParseError> {
+        Self::from_framed_reader_with(reader, ParseOptions::new())
+    }
+    /// read a single length prefixed JSON message like [`Deserialize::from_framed_reader`]
+    /// while using the provided [`ParseOptions`].
+    /// A prefix larger than [`ParseOptions::with_max_length`] is rejected before the message is read
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use wjp::{Deserialize, ParseOptions};
+    ///
+    /// let mut forged = Cursor::new(u32::MAX.to_be_bytes());
+    /// let options = ParseOptions::new().with_max_length(1024);
+    /// assert!(String::from_framed_reader_with(&mut forged, options).is_err());
+    /// ```
+    fn from_framed_reader_with<R: Read>(
+        reader: &mut R,
+        options: ParseOptions,
+    ) -> Result<Self, ParseError> {
+        let mut prefix = [0; 4];
+        reader.read_exact(&mut prefix)?;
+        let len = u32::from_be_bytes(prefix) as usize;
+        if let Some(max) = options.max_length() {
+            if len > max {
+                return Err(ParseError::from(format!(
+                    "frame of {} bytes exceeds the maximum of {} bytes",
+                    len, max
+                )));
+            }
+        }
+        let mut buf = Vec::new();
+        reader.take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() < len {
+            return Err(ParseError::new()
+                .with_kind(ParseErrorKind::UnexpectedEof)
+                .with_msg(format!("frame ended after {} of {} bytes", buf.len(), len).as_str()));
+        }
+        Parser::from_bytes(buf.as_slice(), options)
+            .parse()
+            .map(Self::try_from)?
+    }
 }
 
 impl<T> Deserialize for T where T: TryFrom<Values, Error = ParseError> {}
 
+/// converts the provided [`Values`] Object into T.
+/// This is the same as calling `T::try_from(value)`
+///
+/// ```
+/// use wjp::{from_value, Values};
+/// let val: Values = "[1,2]".parse().unwrap();
+/// assert_eq!(from_value::<Vec<u8>>(val), Ok(vec![1, 2]));
+/// ```
+pub fn from_value<T: TryFrom<Values, Error = ParseError>>(value: Values) -> Result<T, ParseError> {
+    T::try_from(value)
+}
+
+/// Trait for checking the invariants of a Struct after it was built by its [`TryFrom<Values>`] impl.
+/// Deserialize into [`Validated<T>`] to run the check right after the conversion
+///
+/// [`TryFrom<Values>`]: TryFrom
+pub trait Validate {
+    /// returns an error if this value violates one of its invariants
+    fn validate(&self) -> Result<(), ParseError>;
+}
+
+/// Wrapper that deserializes T and then calls its [`Validate::validate`] method,
+/// returning the error of the validation if it fails
+///
+/// ```
+/// use wjp::{Deserialize, ParseError, SerializeHelper, Validate, Validated, Values};
+/// struct Window {
+///     start: u32,
+///     end: u32,
+/// }
+/// impl TryFrom<Values> for Window {
+///     type Error = ParseError;
+///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+///         let mut struc = value.get_struct().ok_or(ParseError::new())?;
+///         Ok(Self {
+///             start: struc.map_val("start", u32::try_from)?,
+///             end: struc.map_val("end", u32::try_from)?,
+///         })
+///     }
+/// }
+/// impl Validate for Window {
+///     fn validate(&self) -> Result<(), ParseError> {
+///         match self.start <= self.end {
+///             true => Ok(()),
+///             false => Err(ParseError::new().with_msg("start is after end")),
+///         }
+///     }
+/// }
+/// assert!(Validated::<Window>::deserialize_str("{\"start\":1,\"end\":2}").is_ok());
+/// assert!(Validated::<Window>::deserialize_str("{\"start\":3,\"end\":2}").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Validated<T>(pub T);
+
+impl<T: Validate + TryFrom<Values, Error = ParseError>> TryFrom<Values> for Validated<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let value = T::try_from(value)?;
+        value.validate()?;
+        Ok(Validated(value))
+    }
+}
+
+impl<T> Validated<T> {
+    /// get the validated value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    use crate::deserialize::{from_value, Validate, Validated};
+    use crate::serializer::to_value;
+    use crate::{
+        map, Deserialize, ParseError, ParseErrorKind, ParseOptions, Serialize, SerializeHelper,
+        Values,
+    };
 
     #[test]
     pub fn test() {}
+
+    #[test]
+    pub fn framed_round_trip() {
+        let mut buf = Vec::new();
+        vec![1u8, 2, 3].json_framed_to_writer(&mut buf).unwrap();
+        String::from("second")
+            .json_framed_to_writer(&mut buf)
+            .unwrap();
+        assert_eq!(buf[..4], [0, 0, 0, 7]);
+
+        let mut reader = Cursor::new(buf);
+        assert_eq!(
+            Vec::<u8>::from_framed_reader(&mut reader),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            String::from_framed_reader(&mut reader),
+            Ok(String::from("second"))
+        );
+        assert!(String::from_framed_reader(&mut reader).is_err());
+    }
+
+    #[test]
+    pub fn framed_reader_limits_the_length() {
+        let mut buf = Vec::new();
+        "x".repeat(100).json_framed_to_writer(&mut buf).unwrap();
+        let options = ParseOptions::new().with_max_length(64);
+        let mut reader = Cursor::new(buf.clone());
+        assert_eq!(
+            String::from_framed_reader_with(&mut reader, options),
+            Err(ParseError::from(String::from(
+                "frame of 102 bytes exceeds the maximum of 64 bytes"
+            )))
+        );
+        // the message isn't read once the prefix is rejected
+        assert_eq!(reader.position(), 4);
+        let options = ParseOptions::new().with_max_length(102);
+        let mut reader = Cursor::new(buf);
+        assert!(String::from_framed_reader_with(&mut reader, options).is_ok());
+    }
+
+    #[test]
+    pub fn framed_reader_with_forged_prefix() {
+        let mut forged = u32::MAX.to_be_bytes().to_vec();
+        forged.extend_from_slice(b"\"short\"");
+        let err = String::from_framed_reader(&mut Cursor::new(forged)).unwrap_err();
+        assert!(err.is_eof());
+        assert_eq!(err.to_string(), "frame ended after 7 of 4294967295 bytes");
+    }
+
+    #[test]
+    pub fn bytes_with_invalid_utf8() {
+        assert_eq!(
+            String::deserialize_bytes("\"zauberer\"".as_bytes()),
+            Ok(String::from("zauberer"))
+        );
+        let err = String::deserialize_bytes(b"\"\xE2\x28\xA1\"").unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidUtf8);
+    }
+
+    #[test]
+    pub fn many_values() {
+        assert_eq!(i32::deserialize_many("1 2 3"), Ok(vec![1, 2, 3]));
+        assert_eq!(i32::deserialize_many(" 1, 2 ,3 "), Ok(vec![1, 2, 3]));
+        assert_eq!(i32::deserialize_many(""), Ok(vec![]));
+        let structs = HashMap::<String, i32>::deserialize_many("{\"a\":1} {\"a\":2}").unwrap();
+        assert_eq!(structs.len(), 2);
+        assert_eq!(structs[0]["a"], 1);
+        assert_eq!(structs[1]["a"], 2);
+        assert!(i32::deserialize_many("1 2 x").is_err());
+        assert!(i32::deserialize_many("1,,2").is_err());
+        assert!(i32::deserialize_many(",1").is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Range {
+        start: i32,
+        end: i32,
+    }
+
+    impl TryFrom<Values> for Range {
+        type Error = ParseError;
+        fn try_from(value: Values) -> Result<Self, Self::Error> {
+            let mut struc = value.get_struct().ok_or(ParseError::new())?;
+            Ok(Self {
+                start: struc.map_val("start", i32::try_from)?,
+                end: struc.map_val("end", i32::try_from)?,
+            })
+        }
+    }
+
+    impl Validate for Range {
+        fn validate(&self) -> Result<(), ParseError> {
+            if self.start <= self.end {
+                Ok(())
+            } else {
+                Err(ParseError::from(format!(
+                    "start {} is after end {}",
+                    self.start, self.end
+                )))
+            }
+        }
+    }
+
+    #[test]
+    pub fn validated_runs_validator() {
+        assert_eq!(
+            Validated::<Range>::deserialize_str("{\"start\":-1,\"end\":1}")
+                .map(Validated::into_inner),
+            Ok(Range { start: -1, end: 1 })
+        );
+        assert_eq!(
+            Validated::<Range>::deserialize_str("{\"start\":2,\"end\":1}"),
+            Err(ParseError::from(String::from("start 2 is after end 1")))
+        );
+        let ranges = Vec::<Validated<Range>>::deserialize_str(
+            "[{\"start\":0,\"end\":0},{\"start\":5,\"end\":4}]",
+        );
+        assert!(ranges.is_err());
+    }
+
+    impl Serialize for Range {
+        fn serialize(&self) -> Values {
+            Values::Struct(map!(("start", &self.start), ("end", &self.end)))
+        }
+    }
+
+    #[test]
+    pub fn to_value_and_back() {
+        let range = Range { start: 3, end: 7 };
+        let val = to_value(&range);
+        assert_eq!(val.get_path("end"), Some(&Values::Number(7.0)));
+        assert_eq!(from_value::<Range>(val), Ok(range));
+        assert!(from_value::<Range>(Values::Null).is_err());
+    }
 }
@@ -1,19 +1,125 @@
-use std::fmt::{Debug, Error, Formatter};
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 /// Error Struct that contains different Information's on what went wrong
-#[derive(Eq, PartialOrd, PartialEq, Hash, Clone, Default, Ord)]
-pub struct ParseError(String);
+#[derive(Clone, Default)]
+pub struct ParseError {
+    msg: String,
+    kind: ParseErrorKind,
+    source: Option<Source>,
+}
+
+// The underlying error of a ParseError. It is shared so that ParseError stays Clone
+type Source = Arc<dyn std::error::Error + Send + Sync>;
+
+// The source is ignored when comparing or hashing, so that two errors with the same message are equal
+impl PartialEq for ParseError {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.msg, self.kind) == (&other.msg, other.kind)
+    }
+}
+
+impl Eq for ParseError {}
+
+impl PartialOrd for ParseError {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ParseError {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.msg, self.kind).cmp(&(&other.msg, other.kind))
+    }
+}
+
+impl Hash for ParseError {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.msg.hash(state);
+        self.kind.hash(state);
+    }
+}
+
+/// The different categories a [`ParseError`] can belong to
+#[derive(Debug, Eq, PartialOrd, PartialEq, Hash, Clone, Copy, Default, Ord)]
+pub enum ParseErrorKind {
+    /// Any error that isn't described by one of the other kinds
+    #[default]
+    Custom,
+    /// The input ended before the JSON value was complete.
+    /// Appending more input could make the JSON valid
+    UnexpectedEof,
+    /// The input contains a character that isn't allowed at its position
+    UnexpectedChar,
+    /// A String inside the input contains bytes that aren't valid UTF-8
+    InvalidUtf8,
+}
 
 impl ParseError {
     /// constructs a new ParseError with an empty message
     pub const fn new() -> Self {
-        Self(String::new())
+        Self {
+            msg: String::new(),
+            kind: ParseErrorKind::Custom,
+            source: None,
+        }
     }
     /// replaces the Error Message with a provided Message
     pub fn with_msg(mut self, msg: &str) -> Self {
-        self.0 = String::from(msg);
+        self.msg = String::from(msg);
+        self
+    }
+    /// replaces the [`ParseErrorKind`] with the provided kind
+    pub fn with_kind(mut self, kind: ParseErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+    /// sets the underlying error that caused this error, which is returned by
+    /// [`Error::source`]. The underlying error is ignored when comparing two errors
+    ///
+    /// [`Error::source`]: std::error::Error::source
+    pub fn with_source<E: std::error::Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
         self
     }
+    /// get the [`ParseErrorKind`] of this error
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+    /// returns true if the input ended before the JSON value was complete.
+    /// In this case appending more input and parsing again could succeed
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert!("[1,2".parse::<Values>().unwrap_err().is_eof());
+    /// assert!(!"[1,x".parse::<Values>().unwrap_err().is_eof());
+    /// ```
+    ///
+    /// This allows reading a value that arrives in chunks, e.g. from a nonblocking socket,
+    /// by keeping the received bytes and parsing them again once more data arrived:
+    ///
+    /// ```
+    /// use wjp::{ParseError, Values};
+    /// let chunks = ["{\"spell\":", "\"lumos\"", "}"];
+    /// let mut buffer = String::new();
+    /// let mut parsed = None;
+    /// for chunk in chunks {
+    ///     buffer.push_str(chunk);
+    ///     match buffer.parse::<Values>() {
+    ///         Ok(val) => parsed = Some(val),
+    ///         Err(err) if err.is_eof() => continue,
+    ///         Err(err) => return Err(err),
+    ///     }
+    /// }
+    /// assert_eq!(parsed.unwrap().get_path("spell").unwrap().as_str(), Some("lumos"));
+    /// # Ok::<(), ParseError>(())
+    /// ```
+    pub fn is_eof(&self) -> bool {
+        self.kind == ParseErrorKind::UnexpectedEof
+    }
 }
 
 impl From<Error> for ParseError {
@@ -22,6 +128,18 @@ impl From<Error> for ParseError {
     }
 }
 
+impl From<std::io::Error> for ParseError {
+    fn from(value: std::io::Error) -> Self {
+        ParseError::from(value.to_string()).with_source(value)
+    }
+}
+
+impl From<Infallible> for ParseError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
 impl From<()> for ParseError {
     fn from(_value: ()) -> Self {
         Self::new()
@@ -29,12 +147,59 @@ impl From<()> for ParseError {
 }
 impl From<String> for ParseError {
     fn from(value: String) -> Self {
-        ParseError(value)
+        Self {
+            msg: value,
+            kind: ParseErrorKind::Custom,
+            source: None,
+        }
     }
 }
 
 impl Debug for ParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::num::ParseIntError;
+
+    use crate::{ParseError, SerializeHelper, Values};
+
+    #[test]
+    pub fn wrapped_error_is_source() {
+        let mut struc = std::collections::HashMap::from([(
+            String::from("level"),
+            Values::String(String::from("high")),
+        )]);
+        let err = struc
+            .map_val_and_err("level", |val| {
+                val.get_string().unwrap_or_default().parse::<u8>()
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        let source = err.source().unwrap();
+        assert!(source.downcast_ref::<ParseIntError>().is_some());
+        assert_eq!(
+            err,
+            ParseError::from(String::from("invalid digit found in string"))
+        );
+        assert!(ParseError::new().source().is_none());
     }
 }
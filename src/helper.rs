@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::error::Error;
 
 use crate::error::ParseError;
+use crate::serializer::Serialize;
 use crate::values::Values;
 /// Helper Trait for Serializing JSON
 pub trait SerializeHelper<T> {
@@ -27,6 +28,44 @@ pub trait SerializeHelper<T> {
     /// get a Result of T or [`ParseError`] containing Info why the operation failed.
     /// In this case the function only takes a [`Values`] object and returns an [`Result<T,ParseError>`]
     ///
+    /// Fields that need a custom encoding can provide their own functions with the signatures
+    /// `fn serialize(&T) -> Values` and `fn deserialize(Values) -> Result<T, ParseError>`:
+    /// ```
+    /// use wjp::{map, Deserialize, ParseError, Serialize, SerializeHelper, Values};
+    ///
+    /// mod hex {
+    ///     use wjp::{ParseError, Values};
+    ///     pub fn serialize(val: &u64) -> Values {
+    ///         Values::String(format!("{:x}", val))
+    ///     }
+    ///     pub fn deserialize(val: Values) -> Result<u64, ParseError> {
+    ///         let string = val.get_string().ok_or(ParseError::new())?;
+    ///         u64::from_str_radix(string.as_str(), 16).map_err(|_err| ParseError::new())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Block {
+    ///     id: u64,
+    /// }
+    /// impl Serialize for Block {
+    ///     fn serialize(&self) -> Values {
+    ///         Values::Struct(map!(("id", &hex::serialize(&self.id))))
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Block {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         let mut struc = value.get_struct().ok_or(ParseError::new())?;
+    ///         let id = struc.map_val("id", hex::deserialize)?;
+    ///         Ok(Self { id })
+    ///     }
+    /// }
+    /// let json = Block { id: 255 }.json();
+    /// assert_eq!(json, "{\"id\":\"ff\"}");
+    /// assert_eq!(Block::deserialize_str(json.as_str()), Ok(Block { id: 255 }));
+    /// ```
+    ///
     /// [`Result<T,ParseError>`]: Result
     fn map_val(
         &mut self,
@@ -43,10 +82,11 @@ pub trait SerializeHelper<T> {
         fun: fn(&Values) -> Result<T, ParseError>,
     ) -> Result<T, ParseError>;
     /// get a Result of T or [`ParseError`] containing Info why the operation failed.
-    /// In this case the function only takes a referenced [`Values`] object and returns an [`Result<T,E>`]
+    /// In this case the function only takes a referenced [`Values`] object and returns an [`Result<T,E>`].
+    /// The error E is kept as the [`Error::source`] of the [`ParseError`]
     ///
     /// [`Result<T,E>`]: Result
-    fn map_val_and_err<E: Error>(
+    fn map_val_and_err<E: Error + Send + Sync + 'static>(
         &mut self,
         attr: &str,
         fun: fn(Values) -> Result<T, E>,
@@ -87,9 +127,10 @@ impl<T> SerializeHelper<T> for HashMap<String, Values> {
         attr: &str,
         fun: fn(Values) -> Result<T, ParseError>,
     ) -> Result<T, ParseError> {
-        self.remove(&String::from(attr))
-            .map(fun)
-            .ok_or(ParseError::new())?
+        match self.remove(attr) {
+            Some(val) => fun(val),
+            None => Err(ParseError::new()),
+        }
     }
     fn map_ref_val(
         &mut self,
@@ -100,7 +141,7 @@ impl<T> SerializeHelper<T> for HashMap<String, Values> {
             .map(fun)
             .ok_or(ParseError::new())?
     }
-    fn map_val_and_err<E: Error>(
+    fn map_val_and_err<E: Error + Send + Sync + 'static>(
         &mut self,
         attr: &str,
         fun: fn(Values) -> Result<T, E>,
@@ -108,7 +149,7 @@ impl<T> SerializeHelper<T> for HashMap<String, Values> {
         self.remove(&String::from(attr))
             .map(fun)
             .ok_or(ParseError::new())?
-            .map_err(|_err| ParseError::new())
+            .map_err(|err| ParseError::from(err.to_string()).with_source(err))
     }
     fn map_opt_val(
         &mut self,
@@ -118,3 +159,127 @@ impl<T> SerializeHelper<T> for HashMap<String, Values> {
         self.remove(attr).map(fun).ok_or(ParseError::new())
     }
 }
+
+/// Combines a JSON Array of flag names into a single bitmask.
+/// Every name is resolved with the provided lookup function and
+/// an unknown name or a non [`String`] item results in a [`ParseError`]
+///
+/// ```
+/// use wjp::{flags_from_array, Values};
+///
+/// fn lookup(name: &str) -> Option<u64> {
+///     match name {
+///         "READ" => Some(0b01),
+///         "WRITE" => Some(0b10),
+///         _ => None,
+///     }
+/// }
+/// let flags = Values::Array(vec![
+///     Values::String(String::from("READ")),
+///     Values::String(String::from("WRITE")),
+/// ]);
+/// assert_eq!(flags_from_array(&flags, lookup), Ok(0b11));
+/// ```
+///
+/// [`String`]: Values::String
+pub fn flags_from_array(
+    value: &Values,
+    lookup: fn(&str) -> Option<u64>,
+) -> Result<u64, ParseError> {
+    let Values::Array(arr) = value else {
+        return Err(ParseError::new());
+    };
+    let mut mask = 0;
+    for item in arr {
+        let Values::String(name) = item else {
+            return Err(ParseError::new());
+        };
+        mask |= lookup(name).ok_or(ParseError::new().with_msg(name))?;
+    }
+    Ok(mask)
+}
+
+/// Turns a bitmask back into a JSON Array containing the names of all flags
+/// whose bits are set in the mask. The names are emitted in the order of the provided flags
+///
+/// ```
+/// use wjp::flags_to_array;
+///
+/// let flags = [("READ", 0b01), ("WRITE", 0b10)];
+/// assert_eq!(flags_to_array(0b10, &flags).to_string(), "[\"WRITE\"]");
+/// ```
+pub fn flags_to_array(mask: u64, flags: &[(&str, u64)]) -> Values {
+    Values::Array(
+        flags
+            .iter()
+            .filter(|(_, flag)| *flag != 0 && mask & flag == *flag)
+            .map(|(name, _)| Values::String(String::from(*name)))
+            .collect(),
+    )
+}
+
+/// Serializes the items of a collection as a JSON Array sorted in ascending order.
+/// This gives unordered collections like a [`HashSet`] a deterministic output,
+/// while the [`Serialize`] impl of a [`HashSet`] keeps the order of its iterator
+///
+/// ```
+/// use std::collections::HashSet;
+/// use wjp::serialize_sorted;
+///
+/// let ids: HashSet<u32> = HashSet::from([3, 1, 2]);
+/// assert_eq!(serialize_sorted(&ids).to_string(), "[1,2,3]");
+/// ```
+///
+/// [`HashSet`]: std::collections::HashSet
+pub fn serialize_sorted<'a, T, I>(items: I) -> Values
+where
+    T: Serialize + Ord + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut items: Vec<&T> = items.into_iter().collect();
+    items.sort_unstable();
+    Values::Array(items.into_iter().map(Serialize::serialize).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::helper::{flags_from_array, flags_to_array, serialize_sorted};
+    use crate::values::Values;
+
+    const FLAGS: [(&str, u64); 3] = [("READ", 1), ("WRITE", 2), ("EXECUTE", 4)];
+
+    fn lookup(name: &str) -> Option<u64> {
+        FLAGS
+            .iter()
+            .find(|(flag, _)| *flag == name)
+            .map(|(_, bit)| *bit)
+    }
+
+    #[test]
+    pub fn flags_round_trip() {
+        let arr = flags_to_array(5, &FLAGS);
+        assert_eq!(arr.to_string(), "[\"READ\",\"EXECUTE\"]");
+        assert_eq!(flags_from_array(&arr, lookup), Ok(5));
+    }
+
+    #[test]
+    pub fn flags_unknown_name() {
+        let arr = Values::Array(vec![Values::String(String::from("DELETE"))]);
+        assert!(flags_from_array(&arr, lookup).is_err());
+    }
+
+    #[test]
+    pub fn flags_not_an_array() {
+        assert!(flags_from_array(&Values::Number(1.0), lookup).is_err());
+    }
+
+    #[test]
+    pub fn serialize_sorted_hash_set() {
+        let set: HashSet<u32> = (0..100).rev().collect();
+        let expected = Values::Array((0..100).map(|num| Values::Number(num as f64)).collect());
+        assert_eq!(serialize_sorted(&set), expected);
+        assert_eq!(serialize_sorted(&HashSet::<u32>::new()).to_string(), "[]")
+    }
+}
@@ -50,7 +50,7 @@
 //! let json = example.json();
 //! println!("{}", json);
 //! // And the <Your Type>::deserialize(&str/String) to deserialize it
-//! let back = Example::deserialize(json);
+//! let back = Example::deserialize_str(json.as_str());
 //! println!("{:?}", back);
 //! ```
 //! Output of the Example above:
@@ -62,11 +62,15 @@
 //! ```
 //!
 //!
-pub use deserialize::Deserialize;
-pub use error::ParseError;
-pub use helper::SerializeHelper;
-pub use serializer::Serialize;
-pub use values::Values;
+pub use deserialize::{from_value, Deserialize, Validate, Validated};
+pub use error::{ParseError, ParseErrorKind};
+pub use helper::{flags_from_array, flags_to_array, serialize_sorted, SerializeHelper};
+pub use parser::{DuplicateKeyPolicy, JsonEvent, JsonEvents, ParseOptions};
+pub use schema::Schema;
+pub use serializer::{to_value, ExactInt, Hex, Lazy, OkOrError, Serialize};
+pub use time::{Millis, Rfc3339, SecondsFloat, SignedMillis};
+pub use values::{minify, prettify, ArrayMerge, Redaction, ValueVisitor, Values};
+pub use writer::ArraySerializer;
 pub const NULL: Values = Values::Null;
 pub const TRUE: Values = Values::Boolean(true);
 pub const FALSE: Values = Values::Boolean(false);
@@ -75,7 +79,10 @@ mod error;
 mod helper;
 mod macros;
 mod parser;
+mod schema;
 mod serializer;
 #[cfg(test)]
 mod test;
+mod time;
 mod values;
+mod writer;
@@ -33,9 +33,143 @@ macro_rules! map (
     };
 );
 
+/// This macro implements [`Serialize`] and [`TryFrom<Values>`] for unit and marker types,
+/// which are serialized as `null` and created with their [`Default`] impl when parsing `null`.
+/// Generic parameters are listed in angle brackets before the type
+///
+/// Example:
+/// ```rust
+/// use std::marker::PhantomData;
+/// use wjp::{impl_unit_serialize, Deserialize, Serialize};
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Marker;
+/// impl_unit_serialize!(Marker);
+///
+/// struct Tagged<T>(PhantomData<T>);
+/// impl<T> Default for Tagged<T> {
+///     fn default() -> Self {
+///         Tagged(PhantomData)
+///     }
+/// }
+/// impl_unit_serialize!(<T> Tagged<T>);
+///
+/// assert_eq!(Marker.json(), "null");
+/// assert_eq!(Marker::deserialize_str("null"), Ok(Marker));
+/// assert!(Tagged::<u8>::deserialize_str("1").is_err());
+/// ```
+///
+/// [`Serialize`]: crate::serializer::Serialize
+/// [`TryFrom<Values>`]: TryFrom
+#[macro_export]
+macro_rules! impl_unit_serialize (
+    (<$($gen:ident),+> $ty:ty) => {
+        impl<$($gen),+> $crate::Serialize for $ty {
+            fn serialize(&self) -> $crate::Values {
+                $crate::Values::Null
+            }
+        }
+
+        impl<$($gen),+> TryFrom<$crate::Values> for $ty {
+            type Error = $crate::ParseError;
+            fn try_from(value: $crate::Values) -> Result<Self, Self::Error> {
+                $crate::Values::unit_from(value)
+            }
+        }
+    };
+    ($ty:ty) => {
+        impl $crate::Serialize for $ty {
+            fn serialize(&self) -> $crate::Values {
+                $crate::Values::Null
+            }
+        }
+
+        impl TryFrom<$crate::Values> for $ty {
+            type Error = $crate::ParseError;
+            fn try_from(value: $crate::Values) -> Result<Self, Self::Error> {
+                $crate::Values::unit_from(value)
+            }
+        }
+    };
+);
+
+/// This macro implements [`Serialize`] and [`TryFrom<Values>`] for a tuple struct with a
+/// single field, which is written as its inner field directly instead of a JSON Struct or Array.
+/// The inner type needs to implement both traits with `Error = ParseError`
+///
+/// Example:
+/// ```rust
+/// use wjp::{impl_transparent_serialize, Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Celsius(f64);
+/// impl_transparent_serialize!(Celsius(f64));
+///
+/// assert_eq!(Celsius(23.0).json(), "23");
+/// assert_eq!(Celsius::deserialize_str("23"), Ok(Celsius(23.0)));
+/// ```
+///
+/// [`Serialize`]: crate::serializer::Serialize
+/// [`TryFrom<Values>`]: TryFrom
+#[macro_export]
+macro_rules! impl_transparent_serialize (
+    ($ty:ident($inner:ty)) => {
+        impl $crate::Serialize for $ty {
+            fn serialize(&self) -> $crate::Values {
+                $crate::Serialize::serialize(&self.0)
+            }
+        }
+
+        impl TryFrom<$crate::Values> for $ty {
+            type Error = $crate::ParseError;
+            fn try_from(value: $crate::Values) -> Result<Self, Self::Error> {
+                <$inner>::try_from(value).map($ty)
+            }
+        }
+    };
+);
+
+/// This macro asserts that two JSON Strings describe the same JSON value.
+/// Both sides are parsed into [`Values`] and compared structurally,
+/// so the order of the keys and any whitespace don't matter.
+/// On a mismatch every difference is listed like in [`Values::describe_diff`]
+///
+/// Example:
+/// ```rust
+/// use wjp::assert_json_eq;
+///
+/// assert_json_eq!("{\"a\":1,\"b\":[true]}", "{ \"b\": [true], \"a\": 1.0 }");
+/// ```
+///
+/// [`Values`]: crate::Values
+/// [`Values::describe_diff`]: crate::Values::describe_diff
+#[macro_export]
+macro_rules! assert_json_eq (
+    ($actual:expr, $expected:expr $(,)?) => {{
+        let parse = |side: &str, json: &str| -> $crate::Values {
+            match json.parse() {
+                Ok(val) => val,
+                Err(err) => panic!("{} is not valid JSON: {:?}\n{}", side, err, json),
+            }
+        };
+        let actual = parse("actual", AsRef::<str>::as_ref(&$actual));
+        let expected = parse("expected", AsRef::<str>::as_ref(&$expected));
+        let mut diff = expected.describe_diff(&actual);
+        if !diff.is_empty() {
+            diff.sort();
+            panic!(
+                "JSON values are not equal:\n  {}\nactual: {}\nexpected: {}",
+                diff.join("\n  "),
+                actual,
+                expected
+            );
+        }
+    }};
+);
+
 #[cfg(test)]
 mod tests {
-    use crate::Serialize;
+    use crate::{Deserialize, Serialize};
     use std::collections::HashMap;
 
     #[test]
@@ -48,4 +182,64 @@ mod tests {
         map.insert(String::from("test"), 123.serialize());
         assert_eq!(map!(("test", &123)), map)
     }
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Invisible;
+
+    impl_unit_serialize!(Invisible);
+
+    #[test]
+    pub fn unit_serialize_for_marker_type() {
+        assert_eq!(Invisible.json(), "null");
+        assert_eq!(
+            Vec::<Invisible>::deserialize_str("[null]"),
+            Ok(vec![Invisible])
+        );
+        assert!(Invisible::deserialize_str("{}").is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Celsius(f64);
+
+    impl_transparent_serialize!(Celsius(f64));
+
+    #[derive(Debug, PartialEq)]
+    struct Spells(Vec<String>);
+
+    impl_transparent_serialize!(Spells(Vec<String>));
+
+    #[test]
+    pub fn transparent_serialize_for_newtypes() {
+        assert_eq!(Celsius(23.0).json(), "23");
+        assert_eq!(Celsius::deserialize_str("23"), Ok(Celsius(23.0)));
+        assert!(Celsius::deserialize_str("{\"0\":23}").is_err());
+        let spells = Spells(vec![String::from("lumos")]);
+        assert_eq!(spells.json(), "[\"lumos\"]");
+        assert_eq!(Spells::deserialize_str("[\"lumos\"]"), Ok(spells));
+    }
+
+    #[test]
+    pub fn json_eq_ignores_key_order() {
+        assert_json_eq!(
+            String::from("{\"name\":\"Merlin\",\"spells\":[{\"a\":1,\"b\":2}]}"),
+            "{\"spells\":[{\"b\":2,\"a\":1}],\n \"name\":\"Merlin\"}",
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "JSON values are not equal:\n  at /spells/0/b: expected Number(2), found Number(3)"
+    )]
+    pub fn json_eq_reports_difference() {
+        assert_json_eq!(
+            "{\"spells\":[{\"a\":1,\"b\":3}]}",
+            "{\"spells\":[{\"b\":2,\"a\":1}]}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "actual is not valid JSON")]
+    pub fn json_eq_rejects_invalid_json() {
+        assert_json_eq!("{", "{}");
+    }
 }
@@ -1,16 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
 use std::str::FromStr;
 
-use crate::error::ParseError;
+use crate::error::{ParseError, ParseErrorKind};
 use crate::values::Values;
-use crate::{FALSE, NULL, TRUE};
+use crate::NULL;
 
 #[macro_use]
 pub(crate) mod macros {
     macro_rules! expect_byte {
         ($parser:ident) => {{
             if $parser.is_eof() {
-                return Err(ParseError::new());
+                return $parser.unexpected_eof();
             }
 
             let ch = $parser.read_byte();
@@ -101,156 +102,364 @@ pub(crate) mod macros {
     }
 }
 
+/// Options to change how strictly a JSON String is parsed
+///
+/// ```
+/// use wjp::{Deserialize, ParseOptions};
+///
+/// let options = ParseOptions::new().with_extended_numbers(true);
+/// let parsed = Vec::<f64>::deserialize_str_with("[-Infinity]", options);
+/// assert_eq!(parsed, Ok(vec![f64::NEG_INFINITY]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions {
+    extended_numbers: bool,
+    max_length: Option<usize>,
+    max_elements: Option<usize>,
+    duplicate_keys: DuplicateKeyPolicy,
+}
+
+/// Decides which value is kept if a key occurs multiple times inside of the same JSON Struct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DuplicateKeyPolicy {
+    /// The value of the last occurrence is kept
+    #[default]
+    LastWins,
+    /// The value of the first occurrence is kept
+    FirstWins,
+    /// A duplicate key results in a [`ParseError`]
+    Error,
+}
+
+impl ParseOptions {
+    /// constructs new ParseOptions that only accept JSON as defined in the specification
+    pub const fn new() -> Self {
+        Self {
+            extended_numbers: false,
+            max_length: None,
+            max_elements: None,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+        }
+    }
+    /// allows the `Infinity`, `-Infinity` and `NaN` literals to be parsed as numbers
+    pub const fn with_extended_numbers(mut self, extended_numbers: bool) -> Self {
+        self.extended_numbers = extended_numbers;
+        self
+    }
+    /// rejects every input that is longer than the provided amount of bytes
+    /// before it gets parsed. By default the length is unlimited
+    pub const fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+    /// rejects every input that contains more than the provided amount of values
+    /// counting every Array, Struct and their items across the whole document.
+    /// By default the amount is unlimited
+    pub const fn with_max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = Some(max_elements);
+        self
+    }
+    // The maximum length of the input, which framed readers check before reading a message
+    pub(crate) const fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+    /// decides which value is kept if a key occurs multiple times inside of the same Struct.
+    /// By default the last value wins
+    pub const fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+}
+
+const SNIPPET_LEN: usize = 20;
+
 pub struct Parser {
     byte_ptr: *const u8,
     index: usize,
     length: usize,
+    options: ParseOptions,
+    stack: Vec<Container>,
+    state: State,
+    elements: usize,
+    keys: Vec<HashSet<String>>,
+}
+
+// The container that is being built, the key of the value that is parsed next
+// and whether that value is discarded because its key is a duplicate
+struct StackBlock(Values, String, bool);
+
+// The containers that are currently open while emitting events
+enum Container {
+    Array,
+    Object,
 }
 
-struct StackBlock(Values, String);
+// What the event parser expects to read next
+enum State {
+    Start,
+    Value,
+    FirstItem,
+    FirstKey,
+    AfterValue,
+    Done,
+}
 
 impl<'a> Parser {
     pub fn parse(&mut self) -> Result<Values, ParseError> {
+        let value = self.parse_value()?;
+        expect_eof!(self);
+        Ok(value)
+    }
+    // Parses every JSON value inside of the input until EOF.
+    // The values can be separated by whitespace and a single comma
+    pub fn parse_many(&mut self) -> Result<Vec<Values>, ParseError> {
+        self.check_length()?;
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.is_eof() {
+                return Ok(values);
+            }
+            if !values.is_empty() && self.read_byte() == b',' {
+                self.bump();
+            }
+            self.state = State::Value;
+            self.elements = 0;
+            values.push(self.parse_value()?);
+        }
+    }
+    // The amount of bytes that were consumed so far
+    pub fn position(&self) -> usize {
+        self.index
+    }
+    fn check_length(&self) -> Result<(), ParseError> {
+        if let Some(max) = self.options.max_length {
+            if self.length > max {
+                return Err(ParseError::from(format!(
+                    "input of {} bytes exceeds the maximum of {} bytes",
+                    self.length, max
+                )));
+            }
+        }
+        Ok(())
+    }
+    fn skip_whitespace(&mut self) {
+        while !self.is_eof() && matches!(self.read_byte(), 9..=13 | 32) {
+            self.bump();
+        }
+    }
+    // Builds a single JSON value out of the events and stops right after it
+    fn parse_value(&mut self) -> Result<Values, ParseError> {
         let mut stack = Vec::with_capacity(3);
-        let mut ch = expect_byte_ignore_whitespace!(self);
-
-        'parsing: loop {
-            let mut value = match ch {
-                b'[' => {
-                    ch = expect_byte_ignore_whitespace!(self);
-
-                    if ch != b']' {
-                        stack.push(StackBlock(
-                            Values::Array(Vec::with_capacity(2)),
-                            "UNIMPORTANT".into(),
-                        ));
-                        continue 'parsing;
+        loop {
+            let value = match self.next_event()? {
+                Some(JsonEvent::BeginArray) => {
+                    let array = Values::Array(Vec::with_capacity(2));
+                    stack.push(StackBlock(array, String::new(), false));
+                    continue;
+                }
+                Some(JsonEvent::BeginObject) => {
+                    let object = Values::Struct(HashMap::with_capacity(3));
+                    stack.push(StackBlock(object, String::new(), false));
+                    continue;
+                }
+                Some(JsonEvent::Key(key)) => {
+                    if let Some(StackBlock(Values::Struct(object), index, discard)) =
+                        stack.last_mut()
+                    {
+                        *discard = self.options.duplicate_keys == DuplicateKeyPolicy::FirstWins
+                            && object.contains_key(&key);
+                        *index = key;
                     }
-
-                    Values::Array(Vec::new())
+                    continue;
                 }
-                b'{' => {
-                    ch = expect_byte_ignore_whitespace!(self);
-
-                    if ch != b'}' {
-                        let mut map = HashMap::with_capacity(3);
-
-                        if ch != b'"' {
-                            return self.unexpected_character();
-                        }
-                        let index = self.expect_string()?;
-                        map.insert(index.clone(), NULL);
-                        expect!(self, b':');
-                        stack.push(StackBlock(Values::Struct(map), index));
-
-                        ch = expect_byte_ignore_whitespace!(self);
+                Some(JsonEvent::EndArray | JsonEvent::EndObject) => match stack.pop() {
+                    Some(StackBlock(value, _, _)) => value,
+                    None => unreachable!(),
+                },
+                Some(JsonEvent::Number(num)) => Values::Number(num),
+                Some(JsonEvent::String(string)) => Values::String(string),
+                Some(JsonEvent::Bool(bool)) => Values::Boolean(bool),
+                Some(JsonEvent::Null) => NULL,
+                None => return self.unexpected_eof(),
+            };
 
-                        continue 'parsing;
+            match stack.last_mut() {
+                None => return Ok(value),
+                Some(StackBlock(Values::Array(array), _, _)) => array.push(value),
+                Some(StackBlock(Values::Struct(object), index, discard)) => {
+                    if !*discard {
+                        object.insert(std::mem::take(index), value);
                     }
-
-                    Values::Struct(HashMap::new())
-                }
-                b'"' => Values::String(self.expect_string()?),
-                b'0'..=b'9' => Values::Number(self.expect_number(ch)?),
-                b'-' => {
-                    let ch = expect_byte!(self);
-                    Values::Number(-match ch {
-                        b'0'..=b'9' => self.expect_number(ch)?,
-                        _ => return self.unexpected_character(),
-                    })
-                }
-                b't' => {
-                    expect_sequence!(self, b'r', b'u', b'e');
-                    TRUE
                 }
-                b'f' => {
-                    expect_sequence!(self, b'a', b'l', b's', b'e');
-                    FALSE
+                _ => unreachable!(),
+            }
+        }
+    }
+    // Reads the next event of the document, which is the grammar shared by
+    // the tree parser and JsonEvents
+    fn next_event(&mut self) -> Result<Option<JsonEvent>, ParseError> {
+        let ch = match self.state {
+            State::Done => return Ok(None),
+            State::Start => {
+                self.check_length()?;
+                self.skip_whitespace();
+                if self.is_eof() {
+                    return self.empty_input();
                 }
-                b'n' => {
-                    expect_sequence!(self, b'u', b'l', b'l');
-                    NULL
+                expect_byte!(self)
+            }
+            State::Value => expect_byte_ignore_whitespace!(self),
+            State::FirstItem => {
+                let ch = expect_byte_ignore_whitespace!(self);
+                if ch == b']' {
+                    self.stack.pop();
+                    self.state = State::AfterValue;
+                    return Ok(Some(JsonEvent::EndArray));
                 }
-                _ => return self.unexpected_character(),
-            };
-
-            'popping: loop {
-                match stack.last_mut() {
+                ch
+            }
+            State::FirstKey => {
+                return match expect_byte_ignore_whitespace!(self) {
+                    b'}' => self.end_object(),
+                    b'"' => self.next_key(),
+                    _ => self.unexpected_character(),
+                };
+            }
+            State::AfterValue => {
+                let ch = match self.stack.last() {
                     None => {
                         expect_eof!(self);
-
-                        return Ok(value);
+                        self.state = State::Done;
+                        return Ok(None);
                     }
-
-                    Some(&mut StackBlock(Values::Array(ref mut array), _)) => {
-                        array.insert(0, value);
-
-                        ch = expect_byte_ignore_whitespace!(self);
-
-                        match ch {
-                            b',' => {
-                                ch = expect_byte_ignore_whitespace!(self);
-
-                                continue 'parsing;
-                            }
-                            b']' => {}
-                            _ => return self.unexpected_character(),
-                        }
+                    Some(_) => expect_byte_ignore_whitespace!(self),
+                };
+                return match (self.stack.last(), ch) {
+                    (Some(Container::Array), b',') => {
+                        self.state = State::Value;
+                        self.next_event()
                     }
-
-                    Some(&mut StackBlock(Values::Struct(ref mut object), ref mut index)) => {
-                        object.insert(index.to_string(), value);
-
-                        ch = expect_byte_ignore_whitespace!(self);
-
-                        match ch {
-                            b',' => {
-                                expect!(self, b'"');
-                                let string = self.expect_string()?;
-                                object.insert(string.clone(), Values::Null);
-                                *index = string;
-                                expect!(self, b':');
-
-                                ch = expect_byte_ignore_whitespace!(self);
-
-                                continue 'parsing;
-                            }
-                            b'}' => {}
-                            _ => return self.unexpected_character(),
-                        }
+                    (Some(Container::Object), b',') => {
+                        expect!(self, b'"');
+                        self.next_key()
                     }
-
-                    _ => unreachable!(),
-                }
-
-                value = match stack.pop() {
-                    Some(StackBlock(value, _)) => value,
-                    None => break 'popping,
+                    (Some(Container::Array), b']') => {
+                        self.stack.pop();
+                        Ok(Some(JsonEvent::EndArray))
+                    }
+                    (Some(Container::Object), b'}') => self.end_object(),
+                    _ => self.unexpected_character(),
+                };
+            }
+        };
+        self.elements += 1;
+        if let Some(max) = self.options.max_elements {
+            if self.elements > max {
+                return Err(ParseError::from(format!(
+                    "input contains more than {} values",
+                    max
+                )));
+            }
+        }
+        self.state = State::AfterValue;
+        let event = match ch {
+            b'[' => {
+                self.stack.push(Container::Array);
+                self.state = State::FirstItem;
+                JsonEvent::BeginArray
+            }
+            b'{' => {
+                self.stack.push(Container::Object);
+                if self.options.duplicate_keys == DuplicateKeyPolicy::Error {
+                    self.keys.push(HashSet::new());
                 }
+                self.state = State::FirstKey;
+                JsonEvent::BeginObject
+            }
+            b'"' => JsonEvent::String(self.expect_string()?),
+            b'0'..=b'9' => JsonEvent::Number(self.expect_number(ch)?),
+            b'-' => {
+                let ch = expect_byte!(self);
+                JsonEvent::Number(-match ch {
+                    b'0'..=b'9' => self.expect_number(ch)?,
+                    b'I' if self.options.extended_numbers => self.expect_infinity()?,
+                    _ => return self.unexpected_character(),
+                })
+            }
+            b'I' if self.options.extended_numbers => JsonEvent::Number(self.expect_infinity()?),
+            b'N' if self.options.extended_numbers => {
+                expect_sequence!(self, b'a', b'N');
+                JsonEvent::Number(f64::NAN)
+            }
+            b't' => {
+                expect_sequence!(self, b'r', b'u', b'e');
+                JsonEvent::Bool(true)
+            }
+            b'f' => {
+                expect_sequence!(self, b'a', b'l', b's', b'e');
+                JsonEvent::Bool(false)
+            }
+            b'n' => {
+                expect_sequence!(self, b'u', b'l', b'l');
+                JsonEvent::Null
+            }
+            _ => return self.unexpected_character(),
+        };
+        Ok(Some(event))
+    }
+    fn next_key(&mut self) -> Result<Option<JsonEvent>, ParseError> {
+        let key = self.expect_string()?;
+        if let Some(keys) = self.keys.last_mut() {
+            if !keys.insert(key.clone()) {
+                return Err(ParseError::from(format!("duplicate key \"{}\"", key)));
             }
         }
+        expect!(self, b':');
+        self.state = State::Value;
+        Ok(Some(JsonEvent::Key(key)))
+    }
+    fn end_object(&mut self) -> Result<Option<JsonEvent>, ParseError> {
+        self.stack.pop();
+        self.keys.pop();
+        self.state = State::AfterValue;
+        Ok(Some(JsonEvent::EndObject))
     }
     pub fn new(source: &'a str) -> Self {
+        Self::with_options(source, ParseOptions::new())
+    }
+    pub fn with_options(source: &'a str, options: ParseOptions) -> Self {
+        Self::from_bytes(source.as_bytes(), options)
+    }
+    pub fn from_bytes(source: &'a [u8], options: ParseOptions) -> Self {
         Parser {
             byte_ptr: source.as_ptr(),
             index: 0,
             length: source.len(),
+            options,
+            stack: Vec::new(),
+            state: State::Start,
+            elements: 0,
+            keys: Vec::new(),
         }
     }
+    fn expect_infinity(&mut self) -> Result<f64, ParseError> {
+        expect_sequence!(self, b'n', b'f', b'i', b'n', b'i', b't', b'y');
+        Ok(f64::INFINITY)
+    }
     fn expect_string(&mut self) -> Result<String, ParseError> {
         let mut string = String::new();
         loop {
-            let char = self.read_byte();
+            let char = expect_byte!(self);
             if char == b'"' {
-                self.bump();
                 return Ok(string);
             }
             if char == b'\\' {
-                self.bump();
                 let escaped = expect_byte!(self);
                 let escaped = match escaped {
                     b'u' => {
-                        // Unicode Characters are not supported
+                        let ch = self.expect_unicode_escape()?;
+                        string.push(ch);
                         continue;
                     }
                     b'"' => b'\"',
@@ -263,32 +472,98 @@ impl<'a> Parser {
                     _ => return self.unexpected_character(),
                 };
                 string.push(char::from(escaped));
-            } else {
+            } else if char.is_ascii() {
                 string.push(char::from(char));
-                self.bump();
+            } else {
+                let start = self.index - 1;
+                let len = match char {
+                    0xC0..=0xDF => 2,
+                    0xE0..=0xEF => 3,
+                    0xF0..=0xF7 => 4,
+                    _ => return self.invalid_utf8(start),
+                };
+                let mut buf = [char, 0, 0, 0];
+                for byte in buf.iter_mut().take(len).skip(1) {
+                    *byte = expect_byte!(self);
+                }
+                match std::str::from_utf8(&buf[..len]) {
+                    Ok(str) => string.push_str(str),
+                    Err(_) => return self.invalid_utf8(start),
+                }
             }
         }
     }
 
-    fn expect_number(&mut self, mut num: u8) -> Result<f64, ()> {
-        let mut string = String::from(char::from(num));
-
-        loop {
-            if !self.is_eof() {
-                num = self.read_byte();
-            } else {
-                return f64::from_str(string.as_str()).map_err(|_err| ());
-            }
-            match num {
-                b'\\' | b' ' | b',' | b']' | b'}' | b'\n' | b'\r' => break,
-                _ => {
-                    string.push(char::from(num));
-                    self.bump();
+    // reads the 4 hex digits after `\u` and, for a high surrogate, the `\uXXXX` low surrogate following it
+    fn expect_unicode_escape(&mut self) -> Result<char, ParseError> {
+        let high = self.expect_hex_digits()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if expect_byte!(self) != b'\\' || expect_byte!(self) != b'u' {
+                    return self.unexpected_character();
+                }
+                let low = self.expect_hex_digits()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return self.unexpected_character();
                 }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
             }
+            code => code,
+        };
+        match char::from_u32(code) {
+            Some(ch) => Ok(ch),
+            None => self.unexpected_character(),
+        }
+    }
+    fn expect_hex_digits(&mut self) -> Result<u32, ParseError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = match char::from(expect_byte!(self)).to_digit(16) {
+                Some(digit) => digit,
+                None => return self.unexpected_character(),
+            };
+            code = code * 16 + digit;
         }
+        Ok(code)
+    }
 
-        f64::from_str(string.as_str()).map_err(|_err| ())
+    // Reads the rest of a number following the grammar of RFC 8259, where the first digit
+    // was already consumed. A byte that can't continue the number ends it and is left to the caller,
+    // while a number that ends right after `.`, `e` or the sign of its exponent is incomplete
+    fn expect_number(&mut self, first: u8) -> Result<f64, ParseError> {
+        let start = self.index - 1;
+        if first != b'0' {
+            self.skip_digits();
+        }
+        if !self.is_eof() && self.read_byte() == b'.' {
+            self.bump();
+            self.expect_digits()?;
+        }
+        if !self.is_eof() && matches!(self.read_byte(), b'e' | b'E') {
+            self.bump();
+            if !self.is_eof() && matches!(self.read_byte(), b'+' | b'-') {
+                self.bump();
+            }
+            self.expect_digits()?;
+        }
+        let bytes =
+            unsafe { std::slice::from_raw_parts(self.byte_ptr.add(start), self.index - start) };
+        match std::str::from_utf8(bytes).map(f64::from_str) {
+            Ok(Ok(num)) => Ok(num),
+            _ => self.unexpected_character(),
+        }
+    }
+    fn expect_digits(&mut self) -> Result<(), ParseError> {
+        if !expect_byte!(self).is_ascii_digit() {
+            return self.unexpected_character();
+        }
+        self.skip_digits();
+        Ok(())
+    }
+    fn skip_digits(&mut self) {
+        while !self.is_eof() && self.read_byte().is_ascii_digit() {
+            self.bump();
+        }
     }
 
     fn is_eof(&self) -> bool {
@@ -306,6 +581,434 @@ impl<'a> Parser {
     }
 
     fn unexpected_character<T: Sized>(&mut self) -> Result<T, ParseError> {
-        Err(ParseError::new())
+        let at = self.index.saturating_sub(1);
+        Err(ParseError::new()
+            .with_kind(ParseErrorKind::UnexpectedChar)
+            .with_msg(
+                format!(
+                    "unexpected character at byte {} near {}",
+                    at,
+                    self.snippet(at)
+                )
+                .as_str(),
+            ))
+    }
+
+    // Up to SNIPPET_LEN bytes of the input on each side of the provided index
+    // with a `|` marking the position of the offending byte
+    fn snippet(&self, at: usize) -> String {
+        let at = at.min(self.length);
+        let start = at.saturating_sub(SNIPPET_LEN);
+        let end = at.saturating_add(SNIPPET_LEN).min(self.length);
+        let bytes = unsafe { std::slice::from_raw_parts(self.byte_ptr, self.length) };
+        format!(
+            "{}{}|{}{}",
+            if start > 0 { "..." } else { "" },
+            String::from_utf8_lossy(&bytes[start..at]),
+            String::from_utf8_lossy(&bytes[at..end]),
+            if end < self.length { "..." } else { "" }
+        )
+    }
+
+    fn invalid_utf8<T: Sized>(&mut self, at: usize) -> Result<T, ParseError> {
+        Err(ParseError::new()
+            .with_kind(ParseErrorKind::InvalidUtf8)
+            .with_msg(format!("invalid UTF-8 sequence at byte {}", at).as_str()))
+    }
+
+    // Separate from `unexpected_eof` because an empty input, e.g. an empty response body,
+    // is a common mistake that deserves its own message
+    fn empty_input<T: Sized>(&mut self) -> Result<T, ParseError> {
+        Err(ParseError::new()
+            .with_kind(ParseErrorKind::UnexpectedEof)
+            .with_msg("empty input, expected a JSON value"))
+    }
+
+    fn unexpected_eof<T: Sized>(&mut self) -> Result<T, ParseError> {
+        Err(ParseError::new()
+            .with_kind(ParseErrorKind::UnexpectedEof)
+            .with_msg("unexpected end of input"))
+    }
+}
+
+/// Events emitted by [`JsonEvents`] while walking through a JSON document
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent {
+    /// Start of a JSON Struct: `{`
+    BeginObject,
+    /// Key of the next entry inside a JSON Struct
+    Key(String),
+    /// End of a JSON Struct: `}`
+    EndObject,
+    /// Start of a JSON Array: `[`
+    BeginArray,
+    /// End of a JSON Array: `]`
+    EndArray,
+    /// A JSON Number
+    Number(f64),
+    /// A JSON String
+    String(String),
+    /// The JSON Value "true" or "false"
+    Bool(bool),
+    /// The JSON Value "null"
+    Null,
+}
+
+/// Iterator over the [`JsonEvent`]s of a JSON document.
+///
+/// This allows walking through a document without building the [`Values`] hierarchy.
+/// The tree parser is built on the same events, so both accept exactly the same input
+/// ```
+/// use wjp::{JsonEvent, JsonEvents};
+///
+/// let events: Result<Vec<JsonEvent>, _> = JsonEvents::new("[1,null]").collect();
+/// assert_eq!(
+///     events.unwrap(),
+///     vec![
+///         JsonEvent::BeginArray,
+///         JsonEvent::Number(1.0),
+///         JsonEvent::Null,
+///         JsonEvent::EndArray
+///     ]
+/// );
+/// ```
+pub struct JsonEvents<'a> {
+    parser: Parser,
+    _source: PhantomData<&'a str>,
+}
+
+impl<'a> JsonEvents<'a> {
+    /// constructs a new Iterator over the events of the provided JSON
+    pub fn new(source: &'a str) -> Self {
+        Self::with_options(source, ParseOptions::new())
+    }
+    /// constructs a new Iterator over the events of the provided JSON that follows the [`ParseOptions`].
+    ///
+    /// Info: Every key is emitted, so with [`DuplicateKeyPolicy::LastWins`] and [`DuplicateKeyPolicy::FirstWins`]
+    /// the consumer decides which value to keep. [`DuplicateKeyPolicy::Error`] stops with an error at the repeated key
+    ///
+    /// ```
+    /// use wjp::{DuplicateKeyPolicy, JsonEvents, ParseOptions};
+    ///
+    /// let options = ParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::Error);
+    /// let mut events = JsonEvents::with_options("{\"a\":1,\"a\":2}", options);
+    /// assert!(events.any(|event| event.is_err()));
+    /// ```
+    pub fn with_options(source: &'a str, options: ParseOptions) -> Self {
+        Self {
+            parser: Parser::with_options(source, options),
+            _source: PhantomData,
+        }
+    }
+    /// returns the amount of bytes of the source that were consumed so far.
+    /// After the last event of a value this is the position right behind that value
+    pub fn position(&self) -> usize {
+        self.parser.position()
+    }
+}
+
+impl Iterator for JsonEvents<'_> {
+    type Item = Result<JsonEvent, ParseError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parser.next_event() {
+            Ok(event) => event.map(Ok),
+            Err(err) => {
+                self.parser.state = State::Done;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseErrorKind;
+    use crate::parser::{DuplicateKeyPolicy, JsonEvent, JsonEvents, ParseOptions, Parser};
+    use crate::values::Values;
+
+    #[test]
+    pub fn extended_numbers_are_rejected_by_default() {
+        assert!(Parser::new("NaN").parse().is_err());
+        assert!(Parser::new("[Infinity]").parse().is_err());
+        assert!(Parser::new("-Infinity").parse().is_err());
+    }
+
+    #[test]
+    pub fn extended_numbers_round_trip() {
+        let options = ParseOptions::new().with_extended_numbers(true);
+        let arr = Values::Array(vec![
+            Values::Number(f64::INFINITY),
+            Values::Number(f64::NEG_INFINITY),
+            Values::Number(f64::NAN),
+        ]);
+        let back = Parser::with_options(arr.to_extended_string().as_str(), options)
+            .parse()
+            .unwrap()
+            .get_list();
+        assert_eq!(back[0], Values::Number(f64::INFINITY));
+        assert_eq!(back[1], Values::Number(f64::NEG_INFINITY));
+        assert!(back[2].get_number().unwrap().is_nan());
+    }
+
+    #[test]
+    pub fn max_length_rejects_oversized_input() {
+        let options = ParseOptions::new().with_max_length(8);
+        assert!(Parser::with_options("[1,2,3,4]", options).parse().is_err());
+        assert!(Parser::with_options("[1,2,3]", options).parse().is_ok());
+        // the input is rejected before it is looked at
+        assert!(
+            Parser::with_options("not json", ParseOptions::new().with_max_length(1))
+                .parse()
+                .is_err_and(|err| format!("{:?}", err).contains("exceeds"))
+        );
+    }
+
+    #[test]
+    pub fn max_elements_rejects_large_documents() {
+        let options = ParseOptions::new().with_max_elements(4);
+        assert!(Parser::with_options("[1,2,3]", options).parse().is_ok());
+        assert!(Parser::with_options("{\"a\":[1],\"b\":2}", options)
+            .parse()
+            .is_ok());
+        assert!(Parser::with_options("[1,2,3,4]", options)
+            .parse()
+            .is_err_and(|err| format!("{:?}", err).contains("more than 4 values")));
+        assert!(Parser::with_options("[[[[[]]]]]", options).parse().is_err());
+    }
+
+    #[test]
+    pub fn unexpected_char_shows_snippet() {
+        let err = Parser::new("{\"key\":\"value\" x}").parse().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            "unexpected character at byte 15 near {\"key\":\"value\" |x}"
+        );
+        let json = format!("[{}x{}]", "1,".repeat(15), ",1".repeat(15));
+        let err = Parser::new(json.as_str()).parse().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            "unexpected character at byte 31 near ...1,1,1,1,1,1,1,1,1,1,|x,1,1,1,1,1,1,1,1,1,..."
+        );
+    }
+
+    #[test]
+    pub fn malformed_number_shows_snippet() {
+        let err = Parser::new("{\"price\":1.5e+x}").parse().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            "unexpected character at byte 14 near {\"price\":1.5e+|x}"
+        );
+        let err = Parser::new("[1x]").parse().unwrap_err();
+        assert_eq!(
+            format!("{:?}", err),
+            "unexpected character at byte 2 near [1|x]"
+        );
+    }
+
+    #[test]
+    pub fn multibyte_strings_are_kept() {
+        let parsed = Parser::new("[\"grüße 🧙\"]").parse().unwrap();
+        assert_eq!(
+            parsed,
+            Values::Array(vec![Values::String(String::from("grüße 🧙"))])
+        );
+    }
+
+    #[test]
+    pub fn unicode_escapes_are_decoded() {
+        let parsed = Parser::new("\"\\u0001\\u00e9\\u20AC\\ud83e\\uddd9\"")
+            .parse()
+            .unwrap();
+        assert_eq!(parsed, Values::String(String::from("\u{1}é€🧙")));
+        for json in [
+            "\"\\u00g1\"",
+            "\"\\ud83e\"",
+            "\"\\ud83e\\u0041\"",
+            "\"\\udddd\"",
+        ] {
+            let err = Parser::new(json).parse().unwrap_err();
+            assert_eq!(err.kind(), ParseErrorKind::UnexpectedChar, "{}", json);
+        }
+    }
+
+    #[test]
+    pub fn invalid_utf8_in_string() {
+        // 0xC3 starts a two byte sequence but 0x28 isn't a continuation byte
+        let bytes = b"{\"name\":\"ab\xC3\x28\"}";
+        let err = Parser::from_bytes(bytes, ParseOptions::new())
+            .parse()
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidUtf8);
+        assert_eq!(format!("{:?}", err), "invalid UTF-8 sequence at byte 11");
+        let stray = b"\"\x80\"";
+        let err = Parser::from_bytes(stray, ParseOptions::new())
+            .parse()
+            .unwrap_err();
+        assert_eq!(err.kind(), ParseErrorKind::InvalidUtf8);
+    }
+
+    #[test]
+    pub fn duplicate_key_policies() {
+        let json = "{\"a\":1,\"b\":[],\"a\":{\"c\":2}}";
+        let parse = |policy| {
+            Parser::with_options(json, ParseOptions::new().with_duplicate_keys(policy)).parse()
+        };
+        let last = parse(DuplicateKeyPolicy::LastWins).unwrap();
+        assert_eq!(last.get_path("a.c"), Some(&Values::Number(2.0)));
+        let first = parse(DuplicateKeyPolicy::FirstWins).unwrap();
+        assert_eq!(first.get_path("a"), Some(&Values::Number(1.0)));
+        assert_eq!(first.get_path("b"), Some(&Values::Array(vec![])));
+        assert!(parse(DuplicateKeyPolicy::Error)
+            .is_err_and(|err| format!("{:?}", err).contains("duplicate key \"a\"")));
+        assert_eq!(Parser::new(json).parse(), Ok(last));
+    }
+
+    #[test]
+    pub fn resume_after_eof_with_grown_buffer() {
+        let mut buffer = String::from("{\"spell\":\"lumos\",\"le");
+        let mut parser = Parser::new(buffer.as_str());
+        assert!(parser.parse().unwrap_err().is_eof());
+        assert_eq!(parser.position(), buffer.len());
+        buffer.push_str("vel\":3}");
+        let parsed = Parser::new(buffer.as_str()).parse().unwrap();
+        assert_eq!(parsed.get_path("level"), Some(&Values::Number(3.0)));
+    }
+
+    #[test]
+    pub fn events_position() {
+        let mut events = JsonEvents::new("[1, 22] ");
+        assert_eq!(events.position(), 0);
+        events.next();
+        assert_eq!(events.position(), 1);
+        events.next();
+        events.next();
+        assert_eq!(events.position(), 6);
+        events.next();
+        assert_eq!(events.position(), 7);
+    }
+
+    #[test]
+    pub fn empty_input_is_eof() {
+        for json in ["", "   ", "\n\t"] {
+            let err = Parser::new(json).parse().unwrap_err();
+            assert!(err.is_eof(), "{:?}", json);
+            assert_eq!(format!("{:?}", err), "empty input, expected a JSON value");
+        }
+        let err = Parser::new("[").parse().unwrap_err();
+        assert_eq!(format!("{:?}", err), "unexpected end of input");
+    }
+
+    #[test]
+    pub fn truncated_input_is_eof() {
+        for json in [
+            "{\"a\": [1, 2",
+            "{\"a\": tru",
+            "\"abc",
+            "[\"a\\",
+            "{\"a\"",
+            "-",
+            "1e",
+            "[1.",
+            "-2E+",
+        ] {
+            let err = Parser::new(json).parse().unwrap_err();
+            assert!(err.is_eof(), "{}", json);
+        }
+    }
+
+    #[test]
+    pub fn malformed_input_is_unexpected_char() {
+        for json in [
+            "{\"a\" x", "[1 2]", "tru3", "{1:2}", "[1]]", "[1x]", "1.x", "[1e+x]", "01", "-.5",
+        ] {
+            let err = Parser::new(json).parse().unwrap_err();
+            assert_eq!(err.kind(), ParseErrorKind::UnexpectedChar, "{}", json);
+            assert!(!err.is_eof());
+        }
+    }
+
+    #[test]
+    pub fn events_of_small_document() {
+        let json = "{\"a\": [1, true, null], \"b\": {}, \"c\": \"x\"}";
+        let events: Vec<JsonEvent> = JsonEvents::new(json).map(Result::unwrap).collect();
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::BeginObject,
+                JsonEvent::Key(String::from("a")),
+                JsonEvent::BeginArray,
+                JsonEvent::Number(1.0),
+                JsonEvent::Bool(true),
+                JsonEvent::Null,
+                JsonEvent::EndArray,
+                JsonEvent::Key(String::from("b")),
+                JsonEvent::BeginObject,
+                JsonEvent::EndObject,
+                JsonEvent::Key(String::from("c")),
+                JsonEvent::String(String::from("x")),
+                JsonEvent::EndObject,
+            ]
+        )
+    }
+
+    #[test]
+    pub fn events_follow_options() {
+        let collect = |json, options| JsonEvents::with_options(json, options).collect::<Vec<_>>();
+        let long = collect("[1,2,3,4]", ParseOptions::new().with_max_length(8));
+        assert_eq!(long.len(), 1);
+        assert!(long[0]
+            .as_ref()
+            .is_err_and(|err| format!("{:?}", err).contains("exceeds")));
+        let many = collect("[1,2,3,4]", ParseOptions::new().with_max_elements(4));
+        assert!(many
+            .last()
+            .unwrap()
+            .as_ref()
+            .is_err_and(|err| format!("{:?}", err).contains("more than 4 values")));
+        let extended = collect(
+            "[-Infinity]",
+            ParseOptions::new().with_extended_numbers(true),
+        );
+        assert_eq!(extended[1], Ok(JsonEvent::Number(f64::NEG_INFINITY)));
+        assert!(JsonEvents::new("[-Infinity]").any(|event| event.is_err()));
+        let json = "{\"a\":{\"a\":1},\"b\":2,\"a\":3}";
+        let keys = collect(json, ParseOptions::new());
+        assert!(keys.iter().all(Result::is_ok));
+        let duplicate = collect(
+            json,
+            ParseOptions::new().with_duplicate_keys(DuplicateKeyPolicy::Error),
+        );
+        assert_eq!(duplicate.iter().filter(|event| event.is_ok()).count(), 8);
+        assert!(duplicate
+            .last()
+            .unwrap()
+            .as_ref()
+            .is_err_and(|err| format!("{:?}", err).contains("duplicate key \"a\"")));
+    }
+
+    #[test]
+    pub fn events_and_tree_reject_the_same_input() {
+        for json in [
+            "",
+            "[1,]",
+            "{\"a\":}",
+            "[1] x",
+            "-x",
+            "[\"\\q\"]",
+            "{\"a\" 1}",
+        ] {
+            let tree = Parser::new(json).parse().map(|_| ());
+            let events = JsonEvents::new(json).try_for_each(|event| event.map(|_| ()));
+            assert_eq!(tree, events, "{}", json);
+        }
+    }
+
+    #[test]
+    pub fn events_stop_after_error() {
+        let mut events = JsonEvents::new("[1 x]");
+        assert_eq!(events.next(), Some(Ok(JsonEvent::BeginArray)));
+        assert_eq!(events.next(), Some(Ok(JsonEvent::Number(1.0))));
+        assert!(events.next().unwrap().is_err());
+        assert_eq!(events.next(), None);
     }
 }
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::error::ParseError;
+use crate::values::Values;
+
+/// Minimal description of the shape of a JSON value, which is checked by [`Values::validate_against`]
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wjp::{Schema, Values};
+///
+/// let schema = Schema::Object(HashMap::from([
+///     (String::from("name"), Schema::String),
+///     (String::from("tags"), Schema::Array(Box::new(Schema::String))),
+///     (String::from("age"), Schema::Optional(Box::new(Schema::Number))),
+/// ]));
+/// let val: Values = "{\"name\":\"Merlin\",\"tags\":[\"wizard\"]}".parse().unwrap();
+/// assert!(val.validate_against(&schema).is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    /// A Struct that contains every listed key with a value matching its Schema.
+    /// Keys that aren't listed are allowed
+    Object(HashMap<String, Schema>),
+    /// An Array whose items all match the Schema
+    Array(Box<Schema>),
+    /// A [`Values::String`]
+    String,
+    /// A [`Values::Number`]
+    Number,
+    /// A [`Values::Boolean`]
+    Bool,
+    /// Either `null`, a missing key or a value matching the Schema
+    Optional(Box<Schema>),
+    /// Any value
+    Any,
+}
+
+impl Values {
+    /// checks that this [`Values`] Object has the shape described by the [`Schema`]
+    /// and returns a [`ParseError`] describing the first violation otherwise.
+    /// The location of the violation is written like a JSON Pointer
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use wjp::{Schema, Values};
+    ///
+    /// let schema = Schema::Object(HashMap::from([(String::from("level"), Schema::Number)]));
+    /// let val: Values = "{\"level\":\"high\"}".parse().unwrap();
+    /// let err = val.validate_against(&schema).unwrap_err();
+    /// assert_eq!(err.to_string(), "at /level: expected number, found string");
+    /// ```
+    pub fn validate_against(&self, schema: &Schema) -> Result<(), ParseError> {
+        validate(self, schema, String::new())
+    }
+}
+
+fn validate(val: &Values, schema: &Schema, path: String) -> Result<(), ParseError> {
+    let mismatch = |expected: &str| {
+        Err(ParseError::from(format!(
+            "at {}: expected {}, found {}",
+            location(&path),
+            expected,
+            val.get_type_as_string()
+        )))
+    };
+    match (schema, val) {
+        (Schema::Any, _)
+        | (Schema::String, Values::String(_))
+        | (Schema::Number, Values::Number(_))
+        | (Schema::Bool, Values::Boolean(_))
+        | (Schema::Optional(_), Values::Null) => Ok(()),
+        (Schema::Optional(inner), _) => validate(val, inner, path),
+        (Schema::Array(items), Values::Array(arr)) => arr
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, item)| validate(item, items, format!("{}/{}", path, i))),
+        (Schema::Object(fields), Values::Struct(map)) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            for key in keys {
+                let field = &fields[key];
+                match map.get(key) {
+                    Some(item) => validate(item, field, format!("{}/{}", path, key))?,
+                    None if matches!(field, Schema::Optional(_) | Schema::Any) => {}
+                    None => return Err(ParseError::from(format!("missing key {}/{}", path, key))),
+                }
+            }
+            Ok(())
+        }
+        (Schema::Object(_), _) => mismatch(Values::STRUCT),
+        (Schema::Array(_), _) => mismatch(Values::ARRAY),
+        (Schema::String, _) => mismatch(Values::STRING),
+        (Schema::Number, _) => mismatch(Values::NUMBER),
+        (Schema::Bool, _) => mismatch(Values::BOOLEAN),
+    }
+}
+
+fn location(path: &str) -> &str {
+    if path.is_empty() {
+        "root"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::schema::Schema;
+    use crate::values::Values;
+
+    fn user_schema() -> Schema {
+        Schema::Object(HashMap::from([
+            (String::from("name"), Schema::String),
+            (String::from("admin"), Schema::Bool),
+            (
+                String::from("sessions"),
+                Schema::Array(Box::new(Schema::Object(HashMap::from([
+                    (String::from("id"), Schema::Number),
+                    (String::from("meta"), Schema::Any),
+                ])))),
+            ),
+            (
+                String::from("email"),
+                Schema::Optional(Box::new(Schema::String)),
+            ),
+        ]))
+    }
+
+    #[test]
+    pub fn passing_document() {
+        for json in [
+            "{\"name\":\"Merlin\",\"admin\":true,\"sessions\":[{\"id\":1,\"meta\":[1]}],\"email\":\"m@camelot\"}",
+            "{\"name\":\"Merlin\",\"admin\":false,\"sessions\":[],\"email\":null,\"extra\":1}",
+        ] {
+            let val: Values = json.parse().unwrap();
+            assert_eq!(val.validate_against(&user_schema()), Ok(()), "{}", json);
+        }
+    }
+
+    #[test]
+    pub fn failing_documents() {
+        let cases = [
+            ("[]", "at root: expected struct, found array"),
+            (
+                "{\"name\":\"Merlin\",\"admin\":1,\"sessions\":[]}",
+                "at /admin: expected boolean, found number",
+            ),
+            (
+                "{\"name\":\"Merlin\",\"admin\":true,\"sessions\":[{\"id\":1},{\"id\":\"2\"}]}",
+                "at /sessions/1/id: expected number, found string",
+            ),
+            (
+                "{\"name\":\"Merlin\",\"sessions\":[]}",
+                "missing key /admin",
+            ),
+            (
+                "{\"name\":\"Merlin\",\"admin\":true,\"sessions\":[],\"email\":5}",
+                "at /email: expected string, found number",
+            ),
+        ];
+        for (json, msg) in cases {
+            let val: Values = json.parse().unwrap();
+            let err = val.validate_against(&user_schema()).unwrap_err();
+            assert_eq!(err.to_string(), msg, "{}", json);
+        }
+    }
+}
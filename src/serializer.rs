@@ -1,13 +1,38 @@
+use std::any::type_name;
+use std::cmp::Reverse;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::hash::Hash;
-use std::isize;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::io::{Error, ErrorKind, Write};
+use std::marker::{PhantomData, PhantomPinned};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use std::ops::{Bound, ControlFlow, Range, RangeFrom, RangeTo};
 use std::str::FromStr;
+use std::sync::atomic::{
+    AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+    AtomicU64, AtomicU8, AtomicUsize, Ordering,
+};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::deserialize::Deserialize;
 use crate::error::ParseError;
+use crate::helper::SerializeHelper;
 use crate::values::Values;
 
 /// Trait for Serializing Rust Structs into JSON
+///
+/// # Integers
+///
+/// Every integer is written as a JSON Number, which is stored as a [`f64`] and therefore only
+/// represents integers up to 2^53 exactly. Wrap bigger [`u64`], [`u128`], [`usize`], [`i64`], [`i128`]
+/// or [`isize`] values into an [`ExactInt`] to write them as a String of their decimal digits beyond that,
+/// e.g. `"18446744073709551615"` for [`u64::MAX`]. The `TryFrom<Values>` impls of every integer type
+/// read both forms exactly, so `"12"` is a valid [`u8`] as well.
 pub trait Serialize {
     /// This method is used to serialize your struct into a Values Object representing a JSON hierarchy
     ///
@@ -44,13 +69,153 @@ pub trait Serialize {
     /// let json = example.json();
     /// println!("{}", json);
     /// ```
+    ///
+    /// Tuple structs are usually written as their inner value if they only wrap a single field
+    /// and as a JSON Array otherwise:
+    ///
+    /// ```rust
+    /// use wjp::{Deserialize, ParseError, Serialize, Values};
+    /// #[derive(Debug, PartialEq)]
+    /// struct Meters(f64);
+    /// #[derive(Debug, PartialEq)]
+    /// struct Point(f64, f64);
+    ///
+    /// impl Serialize for Meters {
+    ///     fn serialize(&self) -> Values {
+    ///         self.0.serialize()
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Meters {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         f64::try_from(value).map(Meters)
+    ///     }
+    /// }
+    /// impl Serialize for Point {
+    ///     fn serialize(&self) -> Values {
+    ///         Values::Array(vec![self.0.serialize(), self.1.serialize()])
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Point {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         let items = value.as_array_of_len(2)?;
+    ///         Ok(Point(
+    ///             f64::try_from(items[0].clone())?,
+    ///             f64::try_from(items[1].clone())?,
+    ///         ))
+    ///     }
+    /// }
+    /// assert_eq!(Meters(1.5).json(), "1.5");
+    /// assert_eq!(Point(1.0, 2.0).json(), "[1,2]");
+    /// assert_eq!(Meters::deserialize_str("1.5"), Ok(Meters(1.5)));
+    /// assert_eq!(Point::deserialize_str("[1,2]"), Ok(Point(1.0, 2.0)));
+    /// ```
+    ///
+    /// The fields of a nested struct can be flattened into the outer JSON Struct
+    /// by extending its serialized Struct. On conflicting keys the outer field wins
+    /// because it is inserted last. The nested struct is read back from the whole Struct:
+    ///
+    /// ```rust
+    /// use wjp::{map, Deserialize, ParseError, Serialize, SerializeHelper, Values};
+    /// #[derive(Debug, PartialEq)]
+    /// struct Metadata {
+    ///     version: u32,
+    /// }
+    /// #[derive(Debug, PartialEq)]
+    /// struct Document {
+    ///     title: String,
+    ///     meta: Metadata,
+    /// }
+    ///
+    /// impl Serialize for Metadata {
+    ///     fn serialize(&self) -> Values {
+    ///         Values::Struct(map!(("version", &self.version)))
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Metadata {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         let mut struc = value.get_struct().ok_or(ParseError::new())?;
+    ///         Ok(Self { version: struc.map_val("version", u32::try_from)? })
+    ///     }
+    /// }
+    /// impl Serialize for Document {
+    ///     fn serialize(&self) -> Values {
+    ///         let mut struc = self.meta.serialize().get_struct().unwrap_or_default();
+    ///         struc.extend(map!(("title", &self.title)));
+    ///         Values::Struct(struc)
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Document {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         let meta = Metadata::try_from(value.clone())?;
+    ///         let mut struc = value.get_struct().ok_or(ParseError::new())?;
+    ///         Ok(Self { title: struc.map_val("title", String::try_from)?, meta })
+    ///     }
+    /// }
+    /// let doc = Document { title: String::from("Spells"), meta: Metadata { version: 2 } };
+    /// let json = doc.json();
+    /// assert_eq!(json.len(), "{\"title\":\"Spells\",\"version\":2}".len());
+    /// assert_eq!(Document::deserialize_str(json.as_str()), Ok(doc));
+    /// ```
+    ///
+    /// Unknown keys can be kept in a catch-all `HashMap<String, Values>` field.
+    /// Since [`SerializeHelper::map_val`] removes the keys it reads,
+    /// the remaining entries of the Struct are exactly the unknown keys:
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use wjp::{map, Deserialize, ParseError, Serialize, SerializeHelper, Values};
+    /// #[derive(Debug, PartialEq)]
+    /// struct Spell {
+    ///     name: String,
+    ///     extra: HashMap<String, Values>,
+    /// }
+    ///
+    /// impl Serialize for Spell {
+    ///     fn serialize(&self) -> Values {
+    ///         let mut struc = self.extra.clone();
+    ///         struc.extend(map!(("name", &self.name)));
+    ///         Values::Struct(struc)
+    ///     }
+    /// }
+    /// impl TryFrom<Values> for Spell {
+    ///     type Error = ParseError;
+    ///     fn try_from(value: Values) -> Result<Self, Self::Error> {
+    ///         let mut struc = value.get_struct().ok_or(ParseError::new())?;
+    ///         let name = struc.map_val("name", String::try_from)?;
+    ///         Ok(Self { name, extra: struc })
+    ///     }
+    /// }
+    /// let spell = Spell::deserialize_str("{\"name\":\"lumos\",\"mana\":5}").unwrap();
+    /// assert_eq!(spell.extra.get("mana"), Some(&Values::Number(5.0)));
+    /// assert_eq!(Spell::deserialize_str(spell.json().as_str()), Ok(spell));
+    /// ```
+    ///
+    /// [`SerializeHelper::map_val`]: crate::SerializeHelper::map_val
     fn serialize(&self) -> Values;
     /// This method has a default impl and it is not advised on writing your own impl for your structs
     fn json(&self) -> String {
-        self.serialize().to_string()
+        self.serialize().to_json_string()
+    }
+    /// This method writes the JSON into the provided writer prefixed with its length in bytes
+    /// as a big endian [`u32`]. These messages can be read with [`Deserialize::from_framed_reader`]
+    fn json_framed_to_writer<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let json = self.json();
+        let len =
+            u32::try_from(json.len()).map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+        writer.write_all(&len.to_be_bytes())?;
+        writer.write_all(json.as_bytes())
     }
 }
 
+/// Serializes [`None`] as `null` and [`Some`] as its inner value.
+///
+/// Info: Nested Options can't be told apart in JSON, so `Some(None)` and `None`
+/// are both serialized as `null`. Use [`Values::into_option`] to read an Option back,
+/// which collapses `null` into the outer [`None`]
 impl<S: Serialize> Serialize for Option<S> {
     fn serialize(&self) -> Values {
         match self {
@@ -60,6 +225,9 @@ impl<S: Serialize> Serialize for Option<S> {
     }
 }
 
+/// Serializes the inner value of either variant without any tag,
+/// so [`Ok`] and [`Err`] can't be told apart in JSON.
+/// Wrap the Result into [`OkOrError`] to keep the variant as `{"ok":..}` or `{"error":..}`
 impl<R: Serialize, E: Serialize> Serialize for Result<R, E> {
     fn serialize(&self) -> Values {
         match self {
@@ -75,36 +243,275 @@ impl<T: Serialize> Serialize for Vec<T> {
     }
 }
 
+/// Serializes a list of key-value pairs as a JSON Struct.
+/// If a key occurs multiple times the last pair wins
+impl<V: Serialize> Serialize for Vec<(String, V)> {
+    fn serialize(&self) -> Values {
+        let mut map = HashMap::with_capacity(self.len());
+        for (k, v) in self {
+            map.insert(k.clone(), v.serialize());
+        }
+        Values::Struct(map)
+    }
+}
+
 impl<T: Serialize> Serialize for &[T] {
     fn serialize(&self) -> Values {
         Values::Array(self.iter().map(|e| e.serialize()).collect())
     }
 }
 
-impl<K: Serialize, V: Serialize> Serialize for HashMap<K, V> {
+impl<T: Serialize> Serialize for [T] {
+    fn serialize(&self) -> Values {
+        Values::Array(self.iter().map(|e| e.serialize()).collect())
+    }
+}
+
+/// Serializes a borrowed list of key-value pairs as a JSON Struct.
+/// Keys are turned into Strings like the keys of a [`HashMap`] and if a key occurs
+/// multiple times the last pair wins
+impl<K: Serialize, V: Serialize> Serialize for &[(K, V)] {
+    fn serialize(&self) -> Values {
+        let mut map = HashMap::with_capacity(self.len());
+        for (k, v) in self.iter() {
+            map.insert(map_key(k), v.serialize());
+        }
+        Values::Struct(map)
+    }
+}
+
+/// Serializes a [`Box`] as its inner value, so a `Box<[T]>` is written like a [`Vec<T>`].
+///
+/// Info: The orphan rules don't allow a generic `TryFrom<Values>` for [`Box`],
+/// so a boxed value is read back with `T::try_from(value).map(Box::new)`
+/// or for recursive types with `struc.map_val("next", Values::into_option::<T>)?.map(Box::new)`.
+/// Only boxed slices can be read directly.
+///
+/// Parsing doesn't recurse, but serializing and above all the `TryFrom` of a recursive type do,
+/// so every level of nesting costs stack. For a small linked list node this is about 1 KiB
+/// per level in a debug build and about 0.5 KiB in a release build, which means
+/// a thread with the default 2 MiB of stack reads lists of around 1,500 nodes.
+///
+/// Warning: Chains of a few thousand nodes aren't supported on a default thread,
+/// since the `TryFrom` of the node calls itself for every `next` and this crate can't make
+/// that call iterative. They need a thread with a bigger stack, see [`std::thread::Builder::stack_size`]
+impl<T: Serialize + ?Sized> Serialize for Box<T> {
+    fn serialize(&self) -> Values {
+        self.as_ref().serialize()
+    }
+}
+
+impl<T: Serialize> Serialize for Reverse<T> {
+    fn serialize(&self) -> Values {
+        self.0.serialize()
+    }
+}
+
+/// converts the provided value into a [`Values`] Object.
+/// This is the same as calling [`Serialize::serialize`] on it
+///
+/// ```
+/// use wjp::{to_value, Values};
+/// assert_eq!(to_value(&vec![1, 2]).to_string(), "[1,2]");
+/// assert_eq!(to_value("wizard"), Values::String(String::from("wizard")));
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Values {
+    value.serialize()
+}
+
+/// Wrapper around a closure that only computes its [`Values`] once it gets serialized.
+/// This avoids computing expensive fields that are never written
+///
+/// ```
+/// use wjp::{map, Lazy, Serialize, Values};
+///
+/// let report = Values::Struct(map!(("total", &Lazy(|| Values::Number(40.0 + 2.0)))));
+/// assert_eq!(report.to_string(), "{\"total\":42}");
+/// ```
+pub struct Lazy<F: Fn() -> Values>(pub F);
+
+impl<F: Fn() -> Values> Serialize for Lazy<F> {
+    fn serialize(&self) -> Values {
+        (self.0)()
+    }
+}
+
+/// Wrapper around bytes that are represented in JSON as a String of lowercase hex digits,
+/// which is common for hashes. Upper- and lowercase digits are accepted when parsing.
+/// Besides a [`Vec<u8>`] any type that can be built from one, like `[u8; 32]`, can be wrapped
+///
+/// ```
+/// use wjp::{Deserialize, Hex, Serialize};
+///
+/// assert_eq!(Hex(vec![0xCA, 0xFE]).json(), "\"cafe\"");
+/// assert_eq!(Hex::<[u8; 2]>::deserialize_str("\"CAFE\""), Ok(Hex([0xCA, 0xFE])));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Hex<T>(pub T);
+
+impl<T: AsRef<[u8]>> Serialize for Hex<T> {
+    fn serialize(&self) -> Values {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let bytes = self.0.as_ref();
+        let mut hex = String::with_capacity(bytes.len() * 2);
+        for byte in bytes {
+            hex.push(DIGITS[(byte >> 4) as usize] as char);
+            hex.push(DIGITS[(byte & 0xF) as usize] as char);
+        }
+        Values::String(hex)
+    }
+}
+
+impl<T: TryFrom<Vec<u8>>> TryFrom<Values> for Hex<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let hex = String::try_from(value)?;
+        if hex.len() % 2 != 0 {
+            return Err(ParseError::from(format!(
+                "hex string \"{}\" has an odd length",
+                hex
+            )));
+        }
+        let bytes = hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let digit = |byte: u8| (byte as char).to_digit(16);
+                match (digit(pair[0]), digit(pair[1])) {
+                    (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                    _ => Err(ParseError::from(format!("invalid hex string \"{}\"", hex))),
+                }
+            })
+            .collect::<Result<Vec<u8>, ParseError>>()?;
+        let len = bytes.len();
+        T::try_from(bytes).map(Hex).map_err(|_err| {
+            ParseError::from(format!("{} bytes don't fit into {}", len, type_name::<T>()))
+        })
+    }
+}
+
+/// Wrapper around an integer that is represented in JSON as a Number as long as a [`f64`]
+/// represents it exactly (up to 2^53) and as a String of its decimal digits beyond that,
+/// so that no precision is lost. The integer types themselves are always written as a Number
+///
+/// ```
+/// use wjp::{Deserialize, ExactInt, Serialize};
+///
+/// assert_eq!(ExactInt(42u128).json(), "42");
+/// assert_eq!(ExactInt(u128::MAX).json(), format!("\"{}\"", u128::MAX));
+/// assert_eq!(ExactInt::<u128>::deserialize_str("\"340282366920938463463374607431768211455\""), Ok(ExactInt(u128::MAX)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ExactInt<T>(pub T);
+
+impl<T: Copy + Display + TryInto<i64>> Serialize for ExactInt<T> {
+    fn serialize(&self) -> Values {
+        wide_integer(self.0)
+    }
+}
+
+impl<T: FromStr> TryFrom<Values> for ExactInt<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        integer_from(value).map(ExactInt)
+    }
+}
+
+/// Wrapper around a [`Result`] that is represented in JSON as a Struct
+/// with a single lowercase key, as used by many REST APIs:
+/// `{"ok":value}` for [`Ok`] and `{"error":err}` for [`Err`]
+///
+/// ```
+/// use wjp::{Deserialize, OkOrError, Serialize};
+///
+/// let ok: OkOrError<u8, String> = OkOrError(Ok(42));
+/// assert_eq!(ok.json(), "{\"ok\":42}");
+/// let err = OkOrError::<u8, String>::deserialize_str("{\"error\":\"no mana\"}");
+/// assert_eq!(err, Ok(OkOrError(Err(String::from("no mana")))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OkOrError<T, E>(pub Result<T, E>);
+
+impl<T: Serialize, E: Serialize> Serialize for OkOrError<T, E> {
+    fn serialize(&self) -> Values {
+        let (key, val) = match &self.0 {
+            Ok(val) => ("ok", val.serialize()),
+            Err(err) => ("error", err.serialize()),
+        };
+        Values::Struct(HashMap::from([(String::from(key), val)]))
+    }
+}
+
+impl<T, E> From<Result<T, E>> for OkOrError<T, E> {
+    fn from(value: Result<T, E>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, E> From<OkOrError<T, E>> for Result<T, E> {
+    fn from(value: OkOrError<T, E>) -> Self {
+        value.0
+    }
+}
+
+/// turns the key of a map into the key of a JSON Struct.
+/// Strings are used as they are and every other value is written as JSON
+fn map_key<K: Serialize>(key: &K) -> String {
+    match key.serialize() {
+        Values::String(string) => string,
+        other => other.to_string(),
+    }
+}
+
+/// Keys that don't serialize to a String are written as their JSON,
+/// so an integer key `42` becomes the Struct key `"42"`
+impl<K: Serialize, V: Serialize, S> Serialize for HashMap<K, V, S> {
     fn serialize(&self) -> Values {
         let mut map = HashMap::with_capacity(4);
         for (k, v) in self.iter() {
-            map.insert(k.serialize().to_string(), v.serialize());
+            map.insert(map_key(k), v.serialize());
         }
         Values::Struct(map)
     }
 }
 
-impl<I: Serialize> Serialize for HashSet<I> {
+impl<I: Serialize, S> Serialize for HashSet<I, S> {
     fn serialize(&self) -> Values {
         Values::Array(self.iter().map(|val| val.serialize()).collect())
     }
 }
 
+/// Only a top-level map is written in the order of its keys: [`json`] called on the map itself
+/// writes its entries, and the entries of BTreeMaps nested directly inside of it, in key order.
+///
+/// Info: Inside of any other value, e.g. a [`Vec`], a [`HashMap`] or a Struct built with [`map!`],
+/// the map is turned into a [`Values::Struct`] by [`serialize`], whose entries are written in no particular order.
+/// Use [`Values::to_canonical_string`] if the keys of the whole document need to be sorted
+///
+/// [`json`]: Serialize::json
+/// [`serialize`]: Serialize::serialize
+/// [`map!`]: crate::map
 impl<K: Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
     fn serialize(&self) -> Values {
         let mut map = HashMap::with_capacity(4);
         for (k, v) in self.iter() {
-            map.insert(k.serialize().to_string(), v.serialize());
+            map.insert(map_key(k), v.serialize());
         }
         Values::Struct(map)
     }
+    fn json(&self) -> String {
+        let mut json = String::from("{");
+        for (i, (k, v)) in self.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            json.push_str(Values::String(map_key(k)).to_string().as_str());
+            json.push(':');
+            json.push_str(v.json().as_str());
+        }
+        json.push('}');
+        json
+    }
 }
 
 impl<I: Serialize> Serialize for BTreeSet<I> {
@@ -143,6 +550,16 @@ impl Serialize for String {
     }
 }
 
+/// Serializes the [`OsString`] as a String.
+///
+/// Info: The conversion is lossy, every part that isn't valid UTF-8
+/// is replaced with `U+FFFD REPLACEMENT CHARACTER`
+impl Serialize for OsString {
+    fn serialize(&self) -> Values {
+        Values::String(self.to_string_lossy().into_owned())
+    }
+}
+
 impl Serialize for char {
     fn serialize(&self) -> Values {
         Serialize::serialize(&self.to_string())
@@ -227,25 +644,321 @@ impl Serialize for i128 {
     }
 }
 
-impl<T: TryFrom<Values>> TryFrom<Values> for Vec<T> {
+/// Serializes the [`Bound`] as a tagged Struct:
+/// `{"Included":x}`, `{"Excluded":x}` or `{"Unbounded":null}`
+impl<T: Serialize> Serialize for Bound<T> {
+    fn serialize(&self) -> Values {
+        let (tag, val) = match self {
+            Bound::Included(val) => ("Included", val.serialize()),
+            Bound::Excluded(val) => ("Excluded", val.serialize()),
+            Bound::Unbounded => ("Unbounded", Values::Null),
+        };
+        Values::Struct(HashMap::from([(String::from(tag), val)]))
+    }
+}
+
+/// Serializes the [`ControlFlow`] as a tagged Struct: `{"Continue":c}` or `{"Break":b}`
+impl<B: Serialize, C: Serialize> Serialize for ControlFlow<B, C> {
+    fn serialize(&self) -> Values {
+        let (tag, val) = match self {
+            ControlFlow::Continue(val) => ("Continue", val.serialize()),
+            ControlFlow::Break(val) => ("Break", val.serialize()),
+        };
+        Values::Struct(HashMap::from([(String::from(tag), val)]))
+    }
+}
+
+/// Serializes the [`Range`] as a Struct: `{"start":a,"end":b}`
+impl<T: Serialize> Serialize for Range<T> {
+    fn serialize(&self) -> Values {
+        Values::Struct(HashMap::from([
+            (String::from("start"), self.start.serialize()),
+            (String::from("end"), self.end.serialize()),
+        ]))
+    }
+}
+
+/// Serializes the [`RangeFrom`] as a Struct: `{"start":a}`
+impl<T: Serialize> Serialize for RangeFrom<T> {
+    fn serialize(&self) -> Values {
+        Values::Struct(HashMap::from([(
+            String::from("start"),
+            self.start.serialize(),
+        )]))
+    }
+}
+
+/// Serializes the [`RangeTo`] as a Struct: `{"end":b}`
+impl<T: Serialize> Serialize for RangeTo<T> {
+    fn serialize(&self) -> Values {
+        Values::Struct(HashMap::from([(String::from("end"), self.end.serialize())]))
+    }
+}
+
+/// Serializes the value inside of the [`Mutex`] while it is locked.
+///
+/// Info: A poisoned Mutex is serialized as well, because [`Serialize`] can't fail
+/// and the data is usually still meaningful for inspection
+impl<T: Serialize + ?Sized> Serialize for Mutex<T> {
+    fn serialize(&self) -> Values {
+        match self.lock() {
+            Ok(guard) => guard.serialize(),
+            Err(poisoned) => poisoned.into_inner().serialize(),
+        }
+    }
+}
+
+/// Serializes the value inside of the [`RwLock`] while it is read locked.
+///
+/// Info: A poisoned RwLock is serialized as well, because [`Serialize`] can't fail
+/// and the data is usually still meaningful for inspection
+impl<T: Serialize + ?Sized> Serialize for RwLock<T> {
+    fn serialize(&self) -> Values {
+        match self.read() {
+            Ok(guard) => guard.serialize(),
+            Err(poisoned) => poisoned.into_inner().serialize(),
+        }
+    }
+}
+
+macro_rules! impl_atomic {
+    ($($atomic:ty => $inner:ty),*) => {
+        $(
+            /// Serializes the current value which is loaded with [`Ordering::Relaxed`]
+            impl Serialize for $atomic {
+                fn serialize(&self) -> Values {
+                    self.load(Ordering::Relaxed).serialize()
+                }
+            }
+
+            /// Creates a new atomic containing the parsed value
+            impl TryFrom<Values> for $atomic {
+                type Error = ParseError;
+                fn try_from(value: Values) -> Result<Self, Self::Error> {
+                    <$inner>::try_from(value).map(<$atomic>::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic!(
+    AtomicBool => bool,
+    AtomicUsize => usize,
+    AtomicU8 => u8,
+    AtomicU16 => u16,
+    AtomicU32 => u32,
+    AtomicU64 => u64,
+    AtomicIsize => isize,
+    AtomicI8 => i8,
+    AtomicI16 => i16,
+    AtomicI32 => i32,
+    AtomicI64 => i64
+);
+
+crate::impl_unit_serialize!(<T> PhantomData<T>);
+crate::impl_unit_serialize!(PhantomPinned);
+
+impl<T: Serialize> Serialize for Wrapping<T> {
+    fn serialize(&self) -> Values {
+        self.0.serialize()
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Wrapping<T> {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        let mut pre = value.get_list_opt().ok_or(ParseError::new())?;
+        T::try_from(value).map(Wrapping)
+    }
+}
+
+macro_rules! impl_non_zero {
+    ($($non_zero:ty => $inner:ty),*) => {
+        $(
+            impl Serialize for $non_zero {
+                fn serialize(&self) -> Values {
+                    self.get().serialize()
+                }
+            }
+
+            /// Parses the inner integer and fails if it is zero
+            impl TryFrom<Values> for $non_zero {
+                type Error = ParseError;
+                fn try_from(value: Values) -> Result<Self, Self::Error> {
+                    <$non_zero>::new(<$inner>::try_from(value)?).ok_or(ParseError::from(format!(
+                        "value 0 out of range for {}",
+                        type_name::<$non_zero>()
+                    )))
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero!(
+    NonZeroUsize => usize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroIsize => isize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128
+);
+
+/// Serializes the [`SystemTime`] as the seconds since the [`UNIX_EPOCH`] with sub-second precision.
+///
+/// Info: Times before the [`UNIX_EPOCH`] aren't supported. Since [`Serialize`] can't fail,
+/// they are written as a negative number instead of being changed silently,
+/// and reading that number back fails with a [`ParseError`]
+impl Serialize for SystemTime {
+    fn serialize(&self) -> Values {
+        Values::Number(match self.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs_f64(),
+            Err(err) => -err.duration().as_secs_f64(),
+        })
+    }
+}
+
+/// Serializes the [`Uuid`] as its hyphenated String representation
+///
+/// [`Uuid`]: uuid::Uuid
+#[cfg(feature = "uuid")]
+impl Serialize for uuid::Uuid {
+    fn serialize(&self) -> Values {
+        Values::String(self.hyphenated().to_string())
+    }
+}
+
+/// Serializes the [`Array2`] as a JSON Array containing one JSON Array per row
+///
+/// [`Array2`]: ndarray::Array2
+#[cfg(feature = "ndarray")]
+impl<A: Serialize> Serialize for ndarray::Array2<A> {
+    fn serialize(&self) -> Values {
+        Values::Array(
+            self.rows()
+                .into_iter()
+                .map(|row| Values::Array(row.iter().map(Serialize::serialize).collect()))
+                .collect(),
+        )
+    }
+}
+
+/// Serializes the [`Decimal`] as a String containing its exact decimal form, e.g. `"0.30"`.
+///
+/// Info: Decimals are string-encoded in both directions. Writing them as a JSON Number
+/// would need a raw number token in [`Values`], which only stores [`f64`] Numbers
+///
+/// [`Decimal`]: rust_decimal::Decimal
+#[cfg(feature = "decimal")]
+impl Serialize for rust_decimal::Decimal {
+    fn serialize(&self) -> Values {
+        Values::String(self.to_string())
+    }
+}
+
+/// The error of an item is kept and prefixed with the index of the item, e.g. `index 2: ...`.
+///
+/// Info: Only a JSON Array is accepted, so a [`Vec<u8>`] isn't decoded from a Base64 String.
+/// Use [`Values::get_bytes`] to read bytes that are sent as either
+impl<T> TryFrom<Values> for Vec<T>
+where
+    T: TryFrom<Values>,
+    ParseError: From<T::Error>,
+{
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let pre = value.get_list_opt().ok_or(ParseError::new())?;
         let mut post = Vec::with_capacity(pre.len());
-        while !pre.is_empty() {
-            post.push(T::try_from(pre.pop().unwrap()).map_err(|_err| ParseError::new())?)
+        for (index, item) in pre.into_iter().enumerate() {
+            let item = T::try_from(item).map_err(|err| {
+                let err = ParseError::from(err);
+                let msg = format!("index {}: {}", index, err);
+                err.with_msg(msg.as_str())
+            })?;
+            post.push(item)
         }
         Ok(post)
     }
 }
 
+impl<T> TryFrom<Values> for Box<[T]>
+where
+    T: TryFrom<Values>,
+    ParseError: From<T::Error>,
+{
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        Vec::try_from(value).map(Vec::into_boxed_slice)
+    }
+}
+
+/// Collects the entries of a JSON Struct into a list of key-value pairs.
+/// The order of the pairs is not guaranteed to match the order in the JSON document
+impl<V: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Vec<(String, V)> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let struc = value.get_struct().ok_or(ParseError::new())?;
+        let mut post = Vec::with_capacity(struc.len());
+        for (key, val) in struc {
+            post.push((key, V::try_from(val)?));
+        }
+        Ok(post)
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Reverse<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        T::try_from(value).map(Reverse)
+    }
+}
+
+/// Reads the variant from the only key of the Struct, which has to be `ok` or `error`
+impl<T, E> TryFrom<Values> for OkOrError<T, E>
+where
+    T: TryFrom<Values, Error = ParseError>,
+    E: TryFrom<Values, Error = ParseError>,
+{
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let (key, val) = tagged(value)?;
+        match key.as_str() {
+            "ok" => T::try_from(val).map(|val| OkOrError(Ok(val))),
+            "error" => E::try_from(val).map(|err| OkOrError(Err(err))),
+            _ => Err(ParseError::from(format!(
+                "expected key \"ok\" or \"error\" but found \"{}\"",
+                key
+            ))),
+        }
+    }
+}
+
+/// Reads a [`char`] from a String that contains exactly one character
 impl TryFrom<Values> for char {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        String::try_from(value)?
-            .chars()
-            .next()
-            .ok_or(ParseError::new())
+        let string = String::try_from(value)?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => Ok(char),
+            _ => Err(ParseError::from(format!(
+                "string \"{}\" is not a single character",
+                string
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Values> for OsString {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        String::try_from(value).map(OsString::from)
     }
 }
 
@@ -270,114 +983,130 @@ impl TryFrom<Values> for f64 {
     }
 }
 
+/// reads an integer from a JSON Number with a descriptive error
+/// if the number is not an integer or doesn't fit into T
+fn integer_from_number<T: FromStr>(value: Values) -> Result<T, ParseError> {
+    let num = f64::try_from(value)?;
+    if num.fract() != 0.0 {
+        return Err(ParseError::from(format!("value {} is not an integer", num)));
+    }
+    T::from_str(num.to_string().as_str()).map_err(|_err| {
+        ParseError::from(format!(
+            "value {} out of range for {}",
+            num,
+            type_name::<T>()
+        ))
+    })
+}
+
+/// the largest integer up to which every integer can be represented exactly by a [`f64`]
+const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
+/// writes the integer as a Number if a [`f64`] can represent it exactly
+/// and as a String of its decimal digits otherwise, so that no precision is lost
+fn wide_integer<T: Copy + Display + TryInto<i64>>(num: T) -> Values {
+    match num.try_into() {
+        Ok(small) if small.unsigned_abs() <= MAX_EXACT_INTEGER => Values::Number(small as f64),
+        _ => Values::String(num.to_string()),
+    }
+}
+
+/// reads the integer from a Number like [`integer_from_number`] or exactly from a String of decimal digits,
+/// which every integer type accepts, since [`ExactInt`] writes wide integers as such a String beyond 2^53
+fn integer_from<T: FromStr>(value: Values) -> Result<T, ParseError> {
+    match value {
+        Values::String(string) => T::from_str(string.as_str()).map_err(|_err| {
+            ParseError::from(format!(
+                "value {} is not a valid {}",
+                string,
+                type_name::<T>()
+            ))
+        }),
+        other => integer_from_number(other),
+    }
+}
+
 impl TryFrom<Values> for usize {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        value
-            .get_number()
-            .map(|f| f.to_string())
-            .map(|s| usize::from_str(s.as_str()))
-            .ok_or(ParseError::new())?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for u8 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        usize::try_from(value)
-            .map(u8::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for u16 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        usize::try_from(value)
-            .map(u16::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for u32 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        usize::try_from(value)
-            .map(u32::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for u64 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        usize::try_from(value)
-            .map(u64::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for u128 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        usize::try_from(value)
-            .map(u128::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for isize {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        String::try_from(value)
-            .map(|str| isize::from_str(str.as_str()))?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for i8 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        isize::try_from(value)
-            .map(i8::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for i16 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        isize::try_from(value)
-            .map(i16::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for i32 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        isize::try_from(value)
-            .map(i32::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for i64 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        isize::try_from(value)
-            .map(i64::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
 impl TryFrom<Values> for i128 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        isize::try_from(value)
-            .map(i128::try_from)?
-            .map_err(|_err| ParseError::new())
+        integer_from(value)
     }
 }
 
@@ -388,34 +1117,219 @@ impl TryFrom<Values> for bool {
     }
 }
 
-impl<K, V> TryFrom<Values> for HashMap<K, V>
+/// splits a tagged Struct with exactly one key into its tag and value
+fn tagged(value: Values) -> Result<(String, Values), ParseError> {
+    let Values::Struct(struc) = value else {
+        return Err(ParseError::from(format!(
+            "expected a Struct with exactly one key, found {}",
+            value.get_type_as_string()
+        )));
+    };
+    if struc.len() != 1 {
+        return Err(ParseError::from(format!(
+            "expected a Struct with exactly one key, found {} keys",
+            struc.len()
+        )));
+    }
+    struc.into_iter().next().ok_or(ParseError::new())
+}
+
+/// the error for a tag that doesn't name any variant of the type
+fn unknown_tag(tag: &str, ty: &str, expected: &str) -> ParseError {
+    ParseError::from(format!(
+        "unknown tag \"{}\" for {}, expected {}",
+        tag, ty, expected
+    ))
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Bound<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let (tag, val) = tagged(value)?;
+        match tag.as_str() {
+            "Included" => T::try_from(val).map(Bound::Included),
+            "Excluded" => T::try_from(val).map(Bound::Excluded),
+            "Unbounded" if val.is_null() => Ok(Bound::Unbounded),
+            "Unbounded" => Err(ParseError::from(format!(
+                "expected null for Unbounded, found {}",
+                val.get_type_as_string()
+            ))),
+            _ => Err(unknown_tag(&tag, "Bound", "Included/Excluded/Unbounded")),
+        }
+    }
+}
+
+impl<B, C> TryFrom<Values> for ControlFlow<B, C>
+where
+    B: TryFrom<Values, Error = ParseError>,
+    C: TryFrom<Values, Error = ParseError>,
+{
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let (tag, val) = tagged(value)?;
+        match tag.as_str() {
+            "Continue" => C::try_from(val).map(ControlFlow::Continue),
+            "Break" => B::try_from(val).map(ControlFlow::Break),
+            _ => Err(unknown_tag(&tag, "ControlFlow", "Continue/Break")),
+        }
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Range<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let mut struc = value.get_struct().ok_or(ParseError::new())?;
+        Ok(struc.map_val("start", T::try_from)?..struc.map_val("end", T::try_from)?)
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for RangeFrom<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let mut struc = value.get_struct().ok_or(ParseError::new())?;
+        Ok(struc.map_val("start", T::try_from)?..)
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for RangeTo<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let mut struc = value.get_struct().ok_or(ParseError::new())?;
+        Ok(..struc.map_val("end", T::try_from)?)
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for Mutex<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        T::try_from(value).map(Mutex::new)
+    }
+}
+
+impl<T: TryFrom<Values, Error = ParseError>> TryFrom<Values> for RwLock<T> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        T::try_from(value).map(RwLock::new)
+    }
+}
+
+/// Reads the seconds since the [`UNIX_EPOCH`].
+/// Negative numbers, which are times before the [`UNIX_EPOCH`], and numbers that
+/// the platform can't represent as a [`SystemTime`] result in a [`ParseError`]
+impl TryFrom<Values> for SystemTime {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let since = f64::try_from(value)?;
+        if since < 0.0 {
+            return Err(ParseError::from(format!(
+                "{} seconds since the UNIX_EPOCH is before the epoch, which SystemTime doesn't support",
+                since
+            )));
+        }
+        Duration::try_from_secs_f64(since)
+            .ok()
+            .and_then(|since| UNIX_EPOCH.checked_add(since))
+            .ok_or_else(|| {
+                ParseError::from(format!(
+                    "{} seconds since the UNIX_EPOCH is out of range for SystemTime",
+                    since
+                ))
+            })
+    }
+}
+
+/// Parses a [`Uuid`] from its hyphenated or simple String representation
+///
+/// [`Uuid`]: uuid::Uuid
+#[cfg(feature = "uuid")]
+impl TryFrom<Values> for uuid::Uuid {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        uuid::Uuid::parse_str(String::try_from(value)?.as_str())
+            .map_err(|err| ParseError::from(err.to_string()))
+    }
+}
+
+/// reads the key of a JSON Struct back into the key of a map.
+/// The key is parsed as a String first and as JSON if that fails
+fn key_from_string<K: TryFrom<Values, Error = ParseError>>(key: String) -> Result<K, ParseError> {
+    match K::try_from(Values::String(key.clone())) {
+        Ok(key) => Ok(key),
+        Err(_err) => K::deserialize_str(key.as_str()),
+    }
+}
+
+/// Parses a JSON Array of rows into an [`Array2`].
+/// Every row needs to have the same length
+///
+/// [`Array2`]: ndarray::Array2
+#[cfg(feature = "ndarray")]
+impl<A: TryFrom<Values, Error = ParseError>> TryFrom<Values> for ndarray::Array2<A> {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let rows = value.get_list_opt().ok_or(ParseError::new())?;
+        let cols = rows.first().map(Values::get_list).unwrap_or_default().len();
+        let mut items = Vec::with_capacity(rows.len() * cols);
+        for row in &rows {
+            for item in row.as_array_of_len(cols)? {
+                items.push(A::try_from(item.clone())?);
+            }
+        }
+        ndarray::Array2::from_shape_vec((rows.len(), cols), items)
+            .map_err(|err| ParseError::from(err.to_string()))
+    }
+}
+
+/// Parses a [`Decimal`] from a String like [`Values::get_string_decimal`].
+/// A JSON Number is rejected, since it was already rounded to a [`f64`] when it was parsed
+///
+/// [`Decimal`]: rust_decimal::Decimal
+#[cfg(feature = "decimal")]
+impl TryFrom<Values> for rust_decimal::Decimal {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        match value {
+            Values::String(string) => rust_decimal::Decimal::from_str(string.as_str())
+                .map_err(|_err| ParseError::from(format!("\"{}\" is not a valid decimal", string))),
+            other => Err(ParseError::from(format!(
+                "expected a decimal String, found {}",
+                other.get_type_as_string()
+            ))),
+        }
+    }
+}
+
+/// Keys are read from the String first and parsed as JSON if that fails,
+/// so a Struct key `"42"` can be read into an integer key
+impl<K, V, S> TryFrom<Values> for HashMap<K, V, S>
 where
     K: TryFrom<Values, Error = ParseError> + Eq + Hash,
-    V: TryFrom<Values, Error = ParseError>,
+    V: TryFrom<Values>,
+    ParseError: From<V::Error>,
+    S: BuildHasher + Default,
 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
-        let mut map = HashMap::new();
+        let mut map = HashMap::default();
         for (key, value) in value.get_struct().ok_or(ParseError::new())? {
-            map.insert(K::deserialize_str(key.as_str())?, V::try_from(value)?);
+            map.insert(key_from_string(key)?, V::try_from(value)?);
         }
         Ok(map)
     }
 }
 
+/// Keys are read like the keys of a [`HashMap`]
 impl<K, V> TryFrom<Values> for BTreeMap<K, V>
 where
-    K: TryFrom<Values, Error = ParseError> + Eq + Hash + Ord,
-    V: TryFrom<Values, Error = ParseError>,
+    K: TryFrom<Values, Error = ParseError> + Ord,
+    V: TryFrom<Values>,
+    ParseError: From<V::Error>,
 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
         let mut map = BTreeMap::new();
         for (key, value) in value.get_struct().ok_or(ParseError::new())? {
-            map.insert(
-                Deserialize::deserialize_str(key.as_str())?,
-                V::try_from(value)?,
-            );
+            map.insert(key_from_string(key)?, V::try_from(value)?);
         }
         Ok(map)
     }
@@ -436,14 +1350,15 @@ where
     }
 }
 
-impl<V> TryFrom<Values> for HashSet<V>
+impl<V, S> TryFrom<Values> for HashSet<V, S>
 where
     V: TryFrom<Values, Error = ParseError> + Hash + Eq,
+    S: BuildHasher + Default,
 {
     type Error = ParseError;
     fn try_from(value: Values) -> Result<Self, Self::Error> {
         let val = value.get_list_opt().ok_or(ParseError::new())?;
-        let mut set = HashSet::new();
+        let mut set = HashSet::default();
         for item in val {
             set.insert(V::try_from(item)?);
         }
@@ -453,10 +1368,23 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::cell::Cell;
+    use std::cmp::Reverse;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeMap, HashMap};
+    use std::ffi::OsString;
     use std::fmt::Display;
+    use std::hash::BuildHasherDefault;
+    use std::marker::{PhantomData, PhantomPinned};
+    use std::num::{NonZeroI32, NonZeroU64, Wrapping};
+    use std::ops::{Bound, ControlFlow, Range, RangeFrom, RangeTo};
+    #[cfg(feature = "decimal")]
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-    use crate::serializer::Serialize;
+    use crate::serializer::{ExactInt, Hex, Lazy, OkOrError, Serialize};
     use crate::{map, Deserialize, ParseError, SerializeHelper, Values};
 
     #[test]
@@ -471,6 +1399,21 @@ mod tests {
         assert_eq!("true", some.serialize().to_string());
     }
 
+    #[test]
+    pub fn test_nested_option_collapses() {
+        let nested: Option<Option<u8>> = Some(None);
+        assert_eq!(nested.json(), "null");
+        assert_eq!(None::<Option<u8>>.json(), "null");
+        let back: Option<Option<u8>> = Values::Null.into_option::<u8>().unwrap().map(Some);
+        assert_eq!(back, None);
+        let back: Option<Option<u8>> = Some(Some(4u8))
+            .serialize()
+            .into_option::<u8>()
+            .unwrap()
+            .map(Some);
+        assert_eq!(back, Some(Some(4)))
+    }
+
     #[test]
     pub fn test_serialized_result_err() {
         let string: Result<&str, &str> = Err("Hello I am a Error");
@@ -495,6 +1438,296 @@ mod tests {
         assert_eq!("[true,false,false,false]", arr.serialize().to_string())
     }
 
+    #[test]
+    pub fn test_deserialized_vec_keeps_order() {
+        let arr = Values::Array(vec![Values::Number(1.0), Values::Number(2.0)]);
+        assert_eq!(Vec::<u8>::try_from(arr), Ok(vec![1, 2]));
+        assert_eq!(Vec::<u8>::deserialize_str("[1,2,3]"), Ok(vec![1, 2, 3]))
+    }
+
+    #[test]
+    pub fn test_byte_vec_needs_an_array() {
+        assert_eq!(Vec::<u8>::deserialize_str("[104,105]"), Ok(vec![104, 105]));
+        assert!(Vec::<u8>::deserialize_str("\"aGk=\"").is_err());
+    }
+
+    #[test]
+    pub fn test_system_time_round_trip() {
+        let now = SystemTime::now();
+        let back = SystemTime::deserialize_str(now.json().as_str()).unwrap();
+        let diff = match back.duration_since(now) {
+            Ok(diff) => diff,
+            Err(err) => err.duration(),
+        };
+        assert!(diff < Duration::from_millis(1))
+    }
+
+    #[test]
+    pub fn test_system_time_before_epoch() {
+        let before = UNIX_EPOCH - Duration::from_secs(10);
+        assert_eq!(before.json(), "-10");
+        assert_eq!(
+            SystemTime::deserialize_str(before.json().as_str()),
+            Err(ParseError::from(String::from(
+                "-10 seconds since the UNIX_EPOCH is before the epoch, which SystemTime doesn't support"
+            )))
+        );
+        let before = UNIX_EPOCH - Duration::from_millis(1500);
+        assert!(SystemTime::deserialize_str(before.json().as_str()).is_err());
+        assert_eq!(SystemTime::deserialize_str("0"), Ok(UNIX_EPOCH));
+    }
+
+    #[test]
+    pub fn test_system_time_out_of_range() {
+        assert_eq!(
+            SystemTime::deserialize_str("1e20").map_err(|err| err.to_string()),
+            Err(String::from(
+                "100000000000000000000 seconds since the UNIX_EPOCH is out of range for SystemTime"
+            ))
+        );
+    }
+
+    #[test]
+    pub fn test_bound_round_trip() {
+        for bound in [Bound::Included(3u8), Bound::Excluded(7), Bound::Unbounded] {
+            assert_eq!(Bound::deserialize_str(bound.json().as_str()), Ok(bound))
+        }
+        assert_eq!(Bound::Included(3u8).json(), "{\"Included\":3}");
+        assert_eq!(Bound::<u8>::Unbounded.json(), "{\"Unbounded\":null}")
+    }
+
+    #[test]
+    pub fn test_bound_unknown_tag() {
+        let message = |json: &str| Bound::<u8>::deserialize_str(json).unwrap_err().to_string();
+        assert_eq!(
+            message("{\"Bogus\":3}"),
+            "unknown tag \"Bogus\" for Bound, expected Included/Excluded/Unbounded"
+        );
+        assert_eq!(
+            message("{\"Included\":3,\"Excluded\":4}"),
+            "expected a Struct with exactly one key, found 2 keys"
+        );
+        assert_eq!(
+            message("{}"),
+            "expected a Struct with exactly one key, found 0 keys"
+        );
+        assert_eq!(
+            message("[3]"),
+            "expected a Struct with exactly one key, found array"
+        );
+        assert_eq!(
+            message("{\"Unbounded\":1}"),
+            "expected null for Unbounded, found number"
+        );
+    }
+
+    #[test]
+    pub fn test_pair_list_round_trip() {
+        let pairs = vec![
+            (String::from("Accept"), String::from("text/html")),
+            (String::from("Host"), String::from("localhost")),
+        ];
+        let json = pairs.json();
+        assert!(json.contains("\"Host\":\"localhost\""));
+        let mut back = Vec::<(String, String)>::deserialize_str(json.as_str()).unwrap();
+        back.sort();
+        assert_eq!(back, pairs)
+    }
+
+    #[test]
+    pub fn test_pair_list_duplicates() {
+        let pairs = vec![(String::from("a"), 1), (String::from("a"), 2)];
+        assert_eq!(pairs.json(), "{\"a\":2}")
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    pub fn test_uuid_round_trip() {
+        let uuid = uuid::Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+        let json = uuid.json();
+        assert_eq!(json, "\"67e55044-10b1-426f-9247-bb680e5fe0c8\"");
+        assert_eq!(uuid::Uuid::deserialize_str(json.as_str()), Ok(uuid));
+        assert_eq!(
+            uuid::Uuid::deserialize_str("\"67e5504410b1426f9247bb680e5fe0c8\""),
+            Ok(uuid)
+        );
+        assert!(uuid::Uuid::deserialize_str("\"67e55044\"").is_err())
+    }
+
+    #[test]
+    pub fn test_integer_out_of_range() {
+        assert_eq!(
+            u8::try_from(Values::Number(300.0)),
+            Err(ParseError::from(String::from(
+                "value 300 out of range for u8"
+            )))
+        );
+        assert_eq!(
+            u32::try_from(Values::Number(-1.0)),
+            Err(ParseError::from(String::from(
+                "value -1 out of range for u32"
+            )))
+        );
+        assert_eq!(
+            i8::try_from(Values::Number(1.5)),
+            Err(ParseError::from(String::from(
+                "value 1.5 is not an integer"
+            )))
+        );
+    }
+
+    #[test]
+    pub fn test_integer_out_of_range_in_collections() {
+        assert_eq!(
+            Vec::<u8>::deserialize_str("[1,300]"),
+            Err(ParseError::from(String::from(
+                "index 1: value 300 out of range for u8"
+            )))
+        );
+        assert_eq!(
+            Vec::<Vec<u8>>::deserialize_str("[[],[0,-1]]"),
+            Err(ParseError::from(String::from(
+                "index 1: index 1: value -1 out of range for u8"
+            )))
+        );
+        assert_eq!(
+            Box::<[u8]>::deserialize_str("[256]").map_err(|err| err.to_string()),
+            Err(String::from("index 0: value 256 out of range for u8"))
+        );
+    }
+
+    #[test]
+    pub fn test_integer_in_range() {
+        assert_eq!(u8::try_from(Values::Number(255.0)), Ok(255));
+        assert_eq!(i16::try_from(Values::Number(-300.0)), Ok(-300));
+        assert_eq!(isize::deserialize_str("-12"), Ok(-12));
+    }
+
+    #[test]
+    pub fn test_atomic_round_trip() {
+        let counter = Arc::new(AtomicU64::new(41));
+        counter.fetch_add(1, Ordering::Relaxed);
+        let json = counter.load(Ordering::Relaxed).json();
+        assert_eq!(AtomicU64::new(42).json(), json);
+        let back = AtomicU64::deserialize_str(json.as_str()).unwrap();
+        assert_eq!(back.load(Ordering::Relaxed), 42);
+        assert_eq!(AtomicBool::new(true).json(), "true")
+    }
+
+    #[test]
+    pub fn test_btree_map_sorted_json() {
+        let mut map = BTreeMap::new();
+        map.insert(10, "ten");
+        map.insert(2, "two");
+        map.insert(7, "seven");
+        assert_eq!(map.json(), "{\"2\":\"two\",\"7\":\"seven\",\"10\":\"ten\"}");
+
+        let nested = BTreeMap::from([("b", map.clone()), ("a", BTreeMap::new())]);
+        assert_eq!(
+            nested.json(),
+            "{\"a\":{},\"b\":{\"2\":\"two\",\"7\":\"seven\",\"10\":\"ten\"}}"
+        );
+    }
+
+    #[test]
+    pub fn test_btree_map_nested_in_other_values() {
+        let map = BTreeMap::from([(10, "ten"), (2, "two"), (7, "seven")]);
+        // inside of a Vec the map is an unordered Values::Struct
+        let list = vec![map.clone()];
+        let Values::Array(items) = list.serialize() else {
+            panic!("expected an array")
+        };
+        assert_eq!(items[0], map.serialize());
+        assert!(matches!(items[0], Values::Struct(_)));
+        assert_eq!(
+            Vec::<BTreeMap<i32, String>>::deserialize_str(list.json().as_str())
+                .map(|list| list[0].keys().copied().collect::<Vec<i32>>()),
+            Ok(vec![2, 7, 10])
+        );
+    }
+
+    #[test]
+    pub fn test_control_flow_round_trip() {
+        let flow: ControlFlow<String, u8> = ControlFlow::Continue(3);
+        assert_eq!(flow.json(), "{\"Continue\":3}");
+        assert_eq!(ControlFlow::deserialize_str(flow.json().as_str()), Ok(flow));
+        let flow: ControlFlow<String, u8> = ControlFlow::Break(String::from("done"));
+        assert_eq!(flow.json(), "{\"Break\":\"done\"}");
+        assert_eq!(ControlFlow::deserialize_str(flow.json().as_str()), Ok(flow));
+    }
+
+    #[test]
+    pub fn test_control_flow_unknown_tag() {
+        let message = |json: &str| {
+            ControlFlow::<String, u8>::deserialize_str(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            message("{\"Stop\":1}"),
+            "unknown tag \"Stop\" for ControlFlow, expected Continue/Break"
+        );
+        assert_eq!(
+            message("{\"Continue\":1,\"Break\":\"done\"}"),
+            "expected a Struct with exactly one key, found 2 keys"
+        );
+        assert_eq!(
+            message("\"Continue\""),
+            "expected a Struct with exactly one key, found string"
+        );
+    }
+
+    #[test]
+    pub fn test_negative_zero_keeps_sign() {
+        let json = (-0.0f64).json();
+        assert_eq!(json, "-0");
+        assert!(f64::deserialize_str(json.as_str())
+            .unwrap()
+            .is_sign_negative());
+        assert!(f32::deserialize_str((-0.0f32).json().as_str())
+            .unwrap()
+            .is_sign_negative());
+        assert!(f64::deserialize_str(0.0f64.json().as_str())
+            .unwrap()
+            .is_sign_positive());
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    pub fn test_ndarray_round_trip() {
+        let matrix = ndarray::array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.5]];
+        let json = matrix.json();
+        assert_eq!(json, "[[1,2,3],[4,5,6.5]]");
+        assert_eq!(
+            ndarray::Array2::<f64>::deserialize_str(json.as_str()),
+            Ok(matrix)
+        )
+    }
+
+    #[test]
+    #[cfg(feature = "ndarray")]
+    pub fn test_ndarray_ragged_input() {
+        assert!(ndarray::Array2::<f64>::deserialize_str("[[1,2,3],[4,5]]").is_err());
+        assert!(ndarray::Array2::<f64>::deserialize_str("[[1,2],3]").is_err())
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    pub fn test_decimal_round_trip() {
+        use rust_decimal::Decimal;
+        let sum = Decimal::from_str("0.1").unwrap() + Decimal::from_str("0.2").unwrap();
+        let json = sum.json();
+        assert_eq!(json, "\"0.3\"");
+        assert_eq!(Decimal::deserialize_str(json.as_str()), Ok(sum));
+        assert_eq!(
+            Decimal::deserialize_str("0.3"),
+            Err(ParseError::from(String::from(
+                "expected a decimal String, found number"
+            )))
+        );
+        assert!(Decimal::deserialize_str("\"0.3.0\"").is_err());
+    }
+
     #[test]
     pub fn test_serialized_map_empty() {
         let map: HashMap<String, String> = map!();
@@ -504,21 +1737,64 @@ mod tests {
     #[test]
     pub fn test_serialized_map_filled() {
         let map = map!(("Hello", &true));
-        assert_eq!("{\"\"Hello\"\":true}", map.serialize().to_string())
+        assert_eq!("{\"Hello\":true}", map.serialize().to_string())
+    }
+
+    #[test]
+    pub fn test_map_with_string_enum_keys() {
+        #[derive(Hash, Eq, PartialEq, Debug)]
+        enum Color {
+            Red,
+            Green,
+        }
+        impl Serialize for Color {
+            fn serialize(&self) -> Values {
+                Values::String(String::from(match self {
+                    Color::Red => "red",
+                    Color::Green => "green",
+                }))
+            }
+        }
+        impl TryFrom<Values> for Color {
+            type Error = ParseError;
+            fn try_from(value: Values) -> Result<Self, Self::Error> {
+                match String::try_from(value)?.as_str() {
+                    "red" => Ok(Color::Red),
+                    "green" => Ok(Color::Green),
+                    _ => Err(ParseError::new()),
+                }
+            }
+        }
+        let map = HashMap::from([(Color::Red, 1u32), (Color::Green, 2)]);
+        let json = map.json();
+        assert!(json.contains("\"red\":1"));
+        assert_eq!(
+            HashMap::<Color, u32>::deserialize_str(json.as_str()),
+            Ok(map)
+        )
+    }
+
+    #[test]
+    pub fn test_map_with_string_keys() {
+        let map = HashMap::from([(String::from("a b"), true)]);
+        assert_eq!(
+            HashMap::<String, bool>::deserialize_str(map.json().as_str()),
+            Ok(map)
+        )
     }
 
     #[test]
     pub fn test_serialized_map_filled_s() {
         #[derive(Hash, Eq, PartialEq, Debug)]
-        struct IDK {
+        struct Idk {
             map: u128,
         }
-        impl Display for IDK {
+        impl Display for Idk {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 write!(f, "{}", self.json())
             }
         }
-        impl TryFrom<Values> for IDK {
+        impl TryFrom<Values> for Idk {
             type Error = ParseError;
             fn try_from(value: Values) -> Result<Self, Self::Error> {
                 let mut struc = value.get_struct().ok_or(ParseError::new())?;
@@ -527,17 +1803,431 @@ mod tests {
                 })
             }
         }
-        impl Serialize for IDK {
+        impl Serialize for Idk {
             fn serialize(&self) -> Values {
                 Values::Struct(map!(("map", &self.map)))
             }
         }
 
         let mut map = HashMap::new();
-        map.insert(100u8, IDK { map: 1 });
+        map.insert(100u8, Idk { map: 1 });
         let ser = map.json();
         println!("{}", ser);
-        let back = HashMap::<u8, IDK>::deserialize_str(ser.as_str());
+        let back = HashMap::<u8, Idk>::deserialize_str(ser.as_str());
         println!("{:?}", back);
     }
+
+    #[test]
+    pub fn hash_map_with_custom_hasher() {
+        type Fixed = BuildHasherDefault<DefaultHasher>;
+        let mut map: HashMap<String, u8, Fixed> = HashMap::default();
+        map.insert(String::from("a"), 1);
+        let json = map.json();
+        assert_eq!(json, "{\"a\":1}");
+        let back = HashMap::<String, u8, Fixed>::deserialize_str(json.as_str());
+        assert_eq!(back, Ok(map));
+    }
+
+    #[test]
+    pub fn recursive_boxed_list_round_trip() {
+        #[derive(Debug, PartialEq)]
+        struct Node {
+            value: u32,
+            next: Option<Box<Node>>,
+        }
+        impl Serialize for Node {
+            fn serialize(&self) -> Values {
+                Values::Struct(map!(("value", &self.value), ("next", &self.next)))
+            }
+        }
+        impl TryFrom<Values> for Node {
+            type Error = ParseError;
+            fn try_from(value: Values) -> Result<Self, Self::Error> {
+                let Values::Struct(mut struc) = value else {
+                    return Err(ParseError::new());
+                };
+                Ok(Self {
+                    value: struc.map_val("value", u32::try_from)?,
+                    next: struc
+                        .map_val("next", Values::into_option::<Node>)?
+                        .map(Box::new),
+                })
+            }
+        }
+
+        // runs on the default test thread and stays below the ~1,500 nodes it supports,
+        // chains of a few thousand nodes aren't supported, see the Box docs
+        let mut list = Node {
+            value: 0,
+            next: None,
+        };
+        for value in 1..1_000 {
+            list = Node {
+                value,
+                next: Some(Box::new(list)),
+            };
+        }
+        let json = list.json();
+        assert!(
+            json.contains("{\"next\":null,\"value\":0}")
+                || json.contains("{\"value\":0,\"next\":null}")
+        );
+        assert_eq!(Node::deserialize_str(json.as_str()), Ok(list));
+    }
+
+    #[test]
+    pub fn reverse_round_trip() {
+        let json = Reverse(5u32).json();
+        assert_eq!(json, 5u32.json());
+        assert_eq!(
+            Reverse::<u32>::deserialize_str(json.as_str()),
+            Ok(Reverse(5))
+        );
+    }
+
+    #[test]
+    pub fn lazy_is_called_once() {
+        let calls = Cell::new(0);
+        let lazy = Lazy(|| {
+            calls.set(calls.get() + 1);
+            Values::Boolean(true)
+        });
+        assert_eq!(calls.get(), 0);
+        assert_eq!(lazy.json(), "true");
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    pub fn wide_integers_round_trip_exactly() {
+        assert_eq!(u128::MAX.serialize(), Values::Number(u128::MAX as f64));
+        assert_eq!(i128::MIN.serialize(), Values::Number(i128::MIN as f64));
+        assert_eq!(ExactInt(u128::MAX).json(), format!("\"{}\"", u128::MAX));
+        assert_eq!(ExactInt(i128::MIN).json(), format!("\"{}\"", i128::MIN));
+        assert_eq!(ExactInt(42u128).json(), "42");
+        assert_eq!(ExactInt(-42i128).json(), "-42");
+        for num in [u128::MAX, u128::MAX - 1, 0, 9_007_199_254_740_993] {
+            let json = ExactInt(num).json();
+            assert_eq!(ExactInt::deserialize_str(json.as_str()), Ok(ExactInt(num)));
+            assert_eq!(u128::deserialize_str(json.as_str()), Ok(num));
+        }
+        for num in [i128::MIN, i128::MAX, -1, -9_007_199_254_740_993] {
+            let json = ExactInt(num).json();
+            assert_eq!(ExactInt::deserialize_str(json.as_str()), Ok(ExactInt(num)));
+            assert_eq!(i128::deserialize_str(json.as_str()), Ok(num));
+        }
+        assert!(u128::deserialize_str("\"-1\"").is_err());
+        assert!(u128::deserialize_str("\"1.5\"").is_err());
+    }
+
+    #[test]
+    pub fn open_ranges_round_trip() {
+        assert_eq!((3u32..).json(), "{\"start\":3}");
+        assert_eq!((..7u32).json(), "{\"end\":7}");
+        assert_eq!(RangeFrom::<u32>::deserialize_str("{\"start\":3}"), Ok(3..));
+        assert_eq!(RangeTo::<u32>::deserialize_str("{\"end\":7}"), Ok(..7));
+        let range = Range::<u32>::deserialize_str((3u32..7).json().as_str());
+        assert_eq!(range, Ok(3..7));
+        assert!(RangeFrom::<u32>::deserialize_str("{\"end\":7}").is_err());
+        assert!(RangeTo::<u32>::deserialize_str("{\"start\":3}").is_err());
+        assert!(Range::<u32>::deserialize_str("{\"start\":3}").is_err());
+    }
+
+    #[test]
+    pub fn char_is_strict() {
+        assert_eq!(char::deserialize_str("\"a\""), Ok('a'));
+        assert_eq!(char::deserialize_str("\"🧙\""), Ok('🧙'));
+        assert_eq!('ß'.json(), "\"ß\"");
+        assert!(char::deserialize_str("\"ab\"").is_err());
+        assert!(char::deserialize_str("\"\"").is_err());
+        assert!(char::deserialize_str("1").is_err());
+    }
+
+    #[test]
+    pub fn values_inside_collections() {
+        let list = vec![Values::Boolean(true), Values::Null, Values::Number(1.5)];
+        assert_eq!(list.json(), "[true,null,1.5]");
+        assert_eq!(Vec::<Values>::deserialize_str("[true,null,1.5]"), Ok(list));
+        let map = HashMap::from([(String::from("a"), Values::Array(vec![]))]);
+        assert_eq!(map.json(), "{\"a\":[]}");
+        assert_eq!(
+            HashMap::<String, Values>::deserialize_str("{\"a\":[]}"),
+            Ok(map)
+        );
+    }
+
+    #[test]
+    pub fn locks_round_trip() {
+        let mutex = Mutex::new(42);
+        assert_eq!(mutex.json(), "42");
+        let back = Mutex::<i32>::deserialize_str("42").unwrap();
+        assert_eq!(*back.lock().unwrap(), 42);
+        let lock = RwLock::new(vec![1, 2]);
+        assert_eq!(lock.json(), "[1,2]");
+        let back = RwLock::<Vec<i32>>::deserialize_str("[1,2]").unwrap();
+        assert_eq!(*back.read().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    pub fn poisoned_mutex_is_serialized() {
+        let mutex = Arc::new(Mutex::new(7));
+        let cloned = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = cloned.lock().unwrap();
+            panic!("poison the lock")
+        })
+        .join();
+        assert!(mutex.is_poisoned());
+        assert_eq!(mutex.json(), "7");
+    }
+
+    #[test]
+    pub fn borrowed_pair_slice() {
+        let pairs = [("a", 1), ("b", 2), ("a", 3)];
+        let slice: &[(&str, i32)] = &pairs;
+        assert_eq!(
+            slice.serialize(),
+            Values::Struct(map!(("a", &3), ("b", &2)))
+        );
+        let numbered: &[(u8, bool)] = &[(1, true)];
+        assert_eq!(numbered.json(), "{\"1\":true}");
+    }
+
+    #[test]
+    pub fn os_string_round_trip() {
+        let os = OsString::from("config/wizard.json");
+        let json = os.json();
+        assert_eq!(json, "\"config/wizard.json\"");
+        assert_eq!(OsString::deserialize_str(json.as_str()), Ok(os));
+        assert!(OsString::deserialize_str("1").is_err());
+    }
+
+    macro_rules! assert_round_trip {
+        ($($num:ty),*) => {
+            $(
+                for num in [<$num>::MIN, <$num>::MAX] {
+                    assert_eq!(
+                        <$num>::deserialize_str(num.json().as_str()),
+                        Ok(num),
+                        "{}",
+                        stringify!($num)
+                    );
+                }
+            )*
+        };
+    }
+
+    macro_rules! assert_exact_round_trip {
+        ($($num:ty),*) => {
+            $(
+                for num in [<$num>::MIN, <$num>::MAX] {
+                    assert_eq!(
+                        ExactInt::<$num>::deserialize_str(ExactInt(num).json().as_str()),
+                        Ok(ExactInt(num)),
+                        "{}",
+                        stringify!($num)
+                    );
+                }
+                let exact = 9_007_199_254_740_992 as $num;
+                assert_eq!(exact.json(), "9007199254740992", "{}", stringify!($num));
+                assert_eq!(<$num>::deserialize_str(exact.json().as_str()), Ok(exact));
+            )*
+        };
+    }
+
+    macro_rules! assert_reads_strings {
+        ($($num:ty),*) => {
+            $(
+                for num in [<$num>::MIN, <$num>::MAX, 12] {
+                    assert_eq!(
+                        <$num>::try_from(Values::String(num.to_string())),
+                        Ok(num),
+                        "{}",
+                        stringify!($num)
+                    );
+                }
+                for invalid in ["1.5", "", " 1", "twelve"] {
+                    assert!(
+                        <$num>::try_from(Values::String(String::from(invalid))).is_err(),
+                        "{} {}",
+                        stringify!($num),
+                        invalid
+                    );
+                }
+            )*
+        };
+    }
+
+    #[test]
+    pub fn numeric_boundaries_round_trip() {
+        assert_round_trip!(u8, u16, u32, i8, i16, i32, f32, f64);
+        assert_exact_round_trip!(u64, u128, usize, i64, i128, isize);
+        for num in [f32::MIN_POSITIVE, f32::EPSILON, -0.0] {
+            assert_eq!(f32::deserialize_str(num.json().as_str()), Ok(num));
+        }
+        for num in [f64::MIN_POSITIVE, f64::EPSILON, -0.0] {
+            assert_eq!(f64::deserialize_str(num.json().as_str()), Ok(num));
+        }
+        assert_eq!(u64::MAX.serialize(), Values::Number(u64::MAX as f64));
+        assert_eq!(i64::MIN.serialize(), Values::Number(i64::MIN as f64));
+        assert_eq!(ExactInt(u64::MAX).json(), format!("\"{}\"", u64::MAX));
+    }
+
+    #[test]
+    pub fn every_integer_reads_strings() {
+        assert_reads_strings!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+        let negative = || Values::String(String::from("-1"));
+        assert!(u8::try_from(negative()).is_err());
+        assert!(u64::try_from(negative()).is_err());
+        assert!(usize::try_from(negative()).is_err());
+        assert_eq!(
+            u8::try_from(Values::String(String::from("256"))),
+            Err(ParseError::from(String::from(
+                "value 256 is not a valid u8"
+            )))
+        );
+    }
+
+    #[test]
+    pub fn wrapping_and_non_zero_round_trip() {
+        let wrapping = Wrapping(u8::MAX) + Wrapping(1);
+        assert_eq!(
+            Wrapping::<u8>::deserialize_str(wrapping.json().as_str()),
+            Ok(wrapping)
+        );
+        let non_zero = NonZeroU64::new(1 << f64::MANTISSA_DIGITS).unwrap();
+        assert_eq!(
+            NonZeroU64::deserialize_str(non_zero.json().as_str()),
+            Ok(non_zero)
+        );
+        assert!(NonZeroI32::deserialize_str("0").is_err());
+    }
+
+    #[test]
+    pub fn ok_or_error_round_trip() {
+        let ok: OkOrError<Vec<u8>, String> = OkOrError(Ok(vec![1, 2]));
+        assert_eq!(ok.json(), "{\"ok\":[1,2]}");
+        assert_eq!(OkOrError::deserialize_str(ok.json().as_str()), Ok(ok));
+        let err: OkOrError<Vec<u8>, String> = OkOrError(Err(String::from("no mana")));
+        assert_eq!(err.json(), "{\"error\":\"no mana\"}");
+        assert_eq!(OkOrError::deserialize_str(err.json().as_str()), Ok(err));
+        assert!(OkOrError::<u8, String>::deserialize_str("{\"Ok\":1}").is_err());
+        assert!(OkOrError::<u8, String>::deserialize_str("{\"ok\":1,\"error\":\"\"}").is_err());
+    }
+
+    #[test]
+    pub fn integer_keyed_map_round_trip() {
+        let map = HashMap::from([(42u32, String::from("answer")), (7, String::from("days"))]);
+        let val = map.serialize();
+        assert_eq!(
+            val.get_path("42"),
+            Some(&Values::String(String::from("answer")))
+        );
+        assert_eq!(HashMap::<u32, String>::try_from(val), Ok(map));
+        let wide = HashMap::from([(1u64 << f64::MANTISSA_DIGITS, true), (0, false)]);
+        assert_eq!(
+            HashMap::<u64, bool>::deserialize_str(wide.json().as_str()),
+            Ok(wide)
+        );
+        assert!(HashMap::<u32, String>::deserialize_str("{\"-1\":\"\"}").is_err());
+    }
+
+    #[test]
+    pub fn hex_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let hex = Hex(bytes.clone());
+        assert_eq!(
+            Hex::<Vec<u8>>::deserialize_str(hex.json().as_str()),
+            Ok(hex)
+        );
+        assert_eq!(Hex(Vec::new()).json(), "\"\"");
+        assert_eq!(
+            Hex::<Vec<u8>>::deserialize_str("\"00fFaB\""),
+            Ok(Hex(vec![0, 255, 171]))
+        );
+        for invalid in ["\"xyz\"", "\"abc\"", "\"zz\"", "\"+1\"", "\"é1\"", "12"] {
+            assert!(
+                Hex::<Vec<u8>>::deserialize_str(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+        assert!(Hex::<[u8; 4]>::deserialize_str("\"cafe\"").is_err());
+    }
+
+    #[test]
+    pub fn phantom_types_are_null() {
+        assert_eq!(PhantomData::<String>.json(), "null");
+        assert_eq!(
+            PhantomData::<String>::deserialize_str("null"),
+            Ok(PhantomData)
+        );
+        assert!(PhantomPinned::deserialize_str("0").is_err());
+    }
+
+    #[test]
+    pub fn btree_map_keys_round_trip() {
+        let series = BTreeMap::from([
+            (1_700_000_000i64, String::from("up")),
+            (-5, String::from("down")),
+        ]);
+        assert_eq!(
+            BTreeMap::deserialize_str(series.json().as_str()),
+            Ok(series)
+        );
+        let padded = BTreeMap::<i64, String>::deserialize_str("{\"007\":\"bond\"}");
+        assert_eq!(padded, Ok(BTreeMap::from([(7, String::from("bond"))])));
+        let names = BTreeMap::from([
+            (String::from("007"), Values::Null),
+            (String::from("true"), Values::Boolean(false)),
+        ]);
+        assert_eq!(
+            BTreeMap::<String, Values>::deserialize_str(names.json().as_str()),
+            Ok(names)
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Wand {
+        wood: String,
+        extra: HashMap<String, Values>,
+    }
+
+    impl Serialize for Wand {
+        fn serialize(&self) -> Values {
+            let mut struc = self.extra.clone();
+            struc.extend(map!(("wood", &self.wood)));
+            Values::Struct(struc)
+        }
+    }
+
+    impl TryFrom<Values> for Wand {
+        type Error = ParseError;
+        fn try_from(value: Values) -> Result<Self, Self::Error> {
+            let mut struc = value.get_struct().ok_or(ParseError::new())?;
+            let wood = struc.map_val("wood", String::try_from)?;
+            Ok(Self { wood, extra: struc })
+        }
+    }
+
+    #[test]
+    pub fn catch_all_field_keeps_unknown_keys() {
+        let json = "{\"wood\":\"holly\",\"core\":\"phoenix\",\"size\":{\"inches\":11}}";
+        let wand = Wand::deserialize_str(json).unwrap();
+        assert_eq!(wand.wood, "holly");
+        assert_eq!(wand.extra.len(), 2);
+        assert_eq!(wand.serialize(), json.parse().unwrap());
+        assert_eq!(Wand::deserialize_str(wand.json().as_str()), Ok(wand));
+    }
+
+    #[test]
+    pub fn boxed_slice_round_trip() {
+        let boxed: Box<[u32]> = Box::new([1, 2, 3]);
+        assert_eq!(boxed.json(), "[1,2,3]");
+        assert_eq!(
+            Box::<[u32]>::deserialize_str(boxed.json().as_str()),
+            Ok(boxed)
+        );
+        assert_eq!(Box::<[u32]>::deserialize_str("[]"), Ok(Box::from([])));
+        assert!(Box::<[u32]>::deserialize_str("[-1]").is_err());
+    }
 }
@@ -0,0 +1,415 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::ParseError;
+use crate::serializer::Serialize;
+use crate::values::Values;
+
+/// Wrapper around [`Duration`] that is represented in JSON as a single
+/// floating point number of seconds, e.g. `1.5` for one and a half seconds
+///
+/// ```
+/// use std::time::Duration;
+/// use wjp::{SecondsFloat, Serialize};
+///
+/// assert_eq!(SecondsFloat(Duration::from_millis(1500)).json(), "1.5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SecondsFloat(pub Duration);
+
+impl Serialize for SecondsFloat {
+    fn serialize(&self) -> Values {
+        Values::Number(self.0.as_secs_f64())
+    }
+}
+
+impl TryFrom<Values> for SecondsFloat {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let secs = value.get_number().ok_or(ParseError::new())?;
+        Duration::try_from_secs_f64(secs)
+            .map(SecondsFloat)
+            .map_err(|_err| ParseError::new())
+    }
+}
+
+impl From<Duration> for SecondsFloat {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SecondsFloat> for Duration {
+    fn from(value: SecondsFloat) -> Self {
+        value.0
+    }
+}
+
+/// Wrapper around [`Duration`] that is represented in JSON as a whole number
+/// of milliseconds, e.g. `1500` for one and a half seconds.
+///
+/// Info: Any precision below a millisecond is truncated when serializing
+///
+/// ```
+/// use std::time::Duration;
+/// use wjp::{Millis, Serialize};
+///
+/// assert_eq!(Millis(Duration::from_micros(1_500_999)).json(), "1500");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Millis(pub Duration);
+
+impl Serialize for Millis {
+    fn serialize(&self) -> Values {
+        Values::Number(self.0.as_millis() as f64)
+    }
+}
+
+impl TryFrom<Values> for Millis {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        u64::try_from(value).map(|millis| Millis(Duration::from_millis(millis)))
+    }
+}
+
+impl From<Duration> for Millis {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Millis> for Duration {
+    fn from(value: Millis) -> Self {
+        value.0
+    }
+}
+
+/// Signed amount of milliseconds for time deltas that can go in either direction,
+/// which a [`Duration`] can't represent. It is written as a whole number, e.g. `-1500`,
+/// which is exact up to ±2^53 milliseconds like every other integer
+///
+/// ```
+/// use std::time::Duration;
+/// use wjp::{Deserialize, SignedMillis, Serialize};
+///
+/// assert_eq!(SignedMillis(-1500).json(), "-1500");
+/// let offset = SignedMillis::deserialize_str("-1500").unwrap();
+/// assert_eq!(offset.magnitude(), Duration::from_millis(1500));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SignedMillis(pub i64);
+
+impl SignedMillis {
+    /// get the absolute length of this delta as a [`Duration`]
+    pub fn magnitude(&self) -> Duration {
+        Duration::from_millis(self.0.unsigned_abs())
+    }
+}
+
+impl Serialize for SignedMillis {
+    fn serialize(&self) -> Values {
+        self.0.serialize()
+    }
+}
+
+impl TryFrom<Values> for SignedMillis {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        i64::try_from(value).map(SignedMillis)
+    }
+}
+
+impl From<i64> for SignedMillis {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SignedMillis> for i64 {
+    fn from(value: SignedMillis) -> Self {
+        value.0
+    }
+}
+
+/// Wrapper around [`SystemTime`] that is represented in JSON as an RFC 3339 String in UTC,
+/// e.g. `"2024-02-29T12:30:00.25Z"`. Fractional seconds are only written if there are any.
+///
+/// Info: Only the UTC designator `Z` is supported, both for writing and for parsing,
+/// and only the years 0000 to 9999 can be represented. Times outside of that range are clamped
+/// to `0000-01-01T00:00:00Z` or `9999-12-31T23:59:59.999999999Z` when written,
+/// so that every written String can be parsed again. Leap seconds aren't supported
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use wjp::{Rfc3339, Serialize};
+///
+/// let time = UNIX_EPOCH + Duration::from_secs(951_782_400);
+/// assert_eq!(Rfc3339(time).json(), "\"2000-02-29T00:00:00Z\"");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rfc3339(pub SystemTime);
+
+const SECS_PER_DAY: i64 = 86_400;
+
+// Converts the days since the UNIX_EPOCH into a (year, month, day) triple of the
+// proleptic Gregorian calendar, see http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// The inverse of `civil_from_days`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Serialize for Rfc3339 {
+    fn serialize(&self) -> Values {
+        let (secs, nanos) = match self.0.duration_since(UNIX_EPOCH) {
+            Ok(since) => (since.as_secs() as i64, since.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                match before.subsec_nanos() {
+                    0 => (-(before.as_secs() as i64), 0),
+                    nanos => (-(before.as_secs() as i64) - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+        let first = days_from_civil(0, 1, 1) * SECS_PER_DAY;
+        let last = days_from_civil(10_000, 1, 1) * SECS_PER_DAY - 1;
+        let (secs, nanos) = if secs < first {
+            (first, 0)
+        } else if secs > last {
+            (last, 999_999_999)
+        } else {
+            (secs, nanos)
+        };
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let time = secs.rem_euclid(SECS_PER_DAY);
+        let mut string = format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        );
+        if nanos != 0 {
+            string.push_str(format!(".{:09}", nanos).trim_end_matches('0'));
+        }
+        string.push('Z');
+        Values::String(string)
+    }
+}
+
+impl TryFrom<Values> for Rfc3339 {
+    type Error = ParseError;
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let string = String::try_from(value)?;
+        let invalid =
+            || ParseError::from(format!("\"{}\" is not a valid RFC 3339 UTC time", string));
+        let bytes = string.as_bytes();
+        let number = |range: std::ops::Range<usize>| -> Result<i64, ParseError> {
+            let digits = bytes.get(range).ok_or_else(invalid)?;
+            if !digits.iter().all(u8::is_ascii_digit) {
+                return Err(invalid());
+            }
+            Ok(digits
+                .iter()
+                .fold(0, |num, digit| num * 10 + i64::from(digit - b'0')))
+        };
+        let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+        if separators
+            .iter()
+            .any(|(at, sep)| bytes.get(*at) != Some(sep))
+            || !matches!(bytes.get(10), Some(b'T' | b't' | b' '))
+            || !matches!(bytes.last(), Some(b'Z' | b'z'))
+        {
+            return Err(invalid());
+        }
+        let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+        let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+        if !(1..=12).contains(&month)
+            || !(1..=days_in_month(year, month)).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(invalid());
+        }
+        let nanos = match &bytes[19..bytes.len() - 1] {
+            [] => 0,
+            [b'.', fraction @ ..] if (1..=9).contains(&fraction.len()) => {
+                number(20..bytes.len() - 1)? * 10i64.pow(9 - fraction.len() as u32)
+            }
+            _ => return Err(invalid()),
+        };
+        let secs =
+            days_from_civil(year, month, day) * SECS_PER_DAY + hour * 3600 + minute * 60 + second;
+        let time = if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos as u32))
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+                .and_then(|time| time.checked_add(Duration::from_nanos(nanos as u64)))
+        };
+        time.map(Rfc3339).ok_or_else(invalid)
+    }
+}
+
+impl From<SystemTime> for Rfc3339 {
+    fn from(value: SystemTime) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Rfc3339> for SystemTime {
+    fn from(value: Rfc3339) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::time::{Millis, Rfc3339, SecondsFloat, SignedMillis};
+    use crate::{Deserialize, Serialize, Values};
+
+    #[test]
+    pub fn seconds_float_round_trip() {
+        let json = SecondsFloat(Duration::new(1, 500_000_000)).json();
+        assert_eq!(json, "1.5");
+        let back = SecondsFloat::deserialize_str(json.as_str()).unwrap();
+        assert_eq!(back.0, Duration::from_millis(1500));
+    }
+
+    #[test]
+    pub fn seconds_float_rejects_invalid() {
+        assert!(SecondsFloat::try_from(Values::Number(-1.0)).is_err());
+        assert!(SecondsFloat::try_from(Values::Number(f64::INFINITY)).is_err());
+        assert!(SecondsFloat::try_from(Values::Number(f64::NAN)).is_err());
+        assert!(SecondsFloat::try_from(Values::Null).is_err());
+    }
+
+    #[test]
+    pub fn millis_round_trip() {
+        let json = Millis(Duration::from_millis(1500)).json();
+        assert_eq!(json, "1500");
+        let back = Millis::deserialize_str(json.as_str()).unwrap();
+        assert_eq!(back.0, Duration::from_millis(1500));
+    }
+
+    #[test]
+    pub fn millis_rejects_invalid() {
+        assert!(Millis::try_from(Values::Number(-1.0)).is_err());
+        assert!(Millis::try_from(Values::Number(1.5)).is_err());
+        assert!(Millis::try_from(Values::Null).is_err());
+    }
+
+    #[test]
+    pub fn signed_millis_round_trip() {
+        let exact = 1 << f64::MANTISSA_DIGITS;
+        for millis in [1500, -1500, 0, -exact, exact] {
+            let delta = SignedMillis(millis);
+            assert_eq!(
+                SignedMillis::deserialize_str(delta.json().as_str()),
+                Ok(delta)
+            );
+        }
+        assert_eq!(SignedMillis(-250).json(), "-250");
+        assert!(SignedMillis::try_from(Values::Number(1.5)).is_err());
+        assert_eq!(SignedMillis(i64::MIN).magnitude().as_millis(), 1 << 63);
+    }
+
+    #[test]
+    pub fn rfc3339_known_offsets() {
+        let cases = [
+            (0, 0, "1970-01-01T00:00:00Z"),
+            (951_782_400, 0, "2000-02-29T00:00:00Z"),
+            (1_709_209_800, 250_000_000, "2024-02-29T12:30:00.25Z"),
+            (4_107_542_399, 1, "2100-02-28T23:59:59.000000001Z"),
+        ];
+        for (secs, nanos, json) in cases {
+            let time = Rfc3339(UNIX_EPOCH + Duration::new(secs, nanos));
+            assert_eq!(time.serialize(), Values::String(String::from(json)));
+            assert_eq!(Rfc3339::try_from(time.serialize()), Ok(time));
+        }
+        let before = Rfc3339(UNIX_EPOCH - Duration::from_millis(1500));
+        assert_eq!(before.json(), "\"1969-12-31T23:59:58.5Z\"");
+        assert_eq!(Rfc3339::deserialize_str(before.json().as_str()), Ok(before));
+    }
+
+    #[test]
+    pub fn rfc3339_clamps_to_four_digit_years() {
+        let last = UNIX_EPOCH + Duration::new(253_402_300_799, 999_999_999);
+        let first = UNIX_EPOCH - Duration::from_secs(62_167_219_200);
+        for (time, json) in [
+            (last, "\"9999-12-31T23:59:59.999999999Z\""),
+            (first, "\"0000-01-01T00:00:00Z\""),
+        ] {
+            assert_eq!(Rfc3339(time).json(), json);
+            assert_eq!(Rfc3339::deserialize_str(json), Ok(Rfc3339(time)));
+        }
+        let after = Rfc3339(last + Duration::from_nanos(1));
+        assert_eq!(Rfc3339::try_from(after.serialize()), Ok(Rfc3339(last)));
+        let far_after = Rfc3339(UNIX_EPOCH + Duration::from_secs(300_000_000_000));
+        assert_eq!(Rfc3339::try_from(far_after.serialize()), Ok(Rfc3339(last)));
+        let before = Rfc3339(first - Duration::from_nanos(1));
+        assert_eq!(before.json(), "\"0000-01-01T00:00:00Z\"");
+        let far_before = Rfc3339(UNIX_EPOCH - Duration::from_secs(70_000_000_000));
+        assert_eq!(
+            Rfc3339::try_from(far_before.serialize()),
+            Ok(Rfc3339(first))
+        );
+    }
+
+    #[test]
+    pub fn rfc3339_rejects_invalid() {
+        for json in [
+            "2023-02-29T00:00:00Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:60Z",
+            "2024-01-01T00:00:00+01:00",
+            "2024-01-01T00:00:00",
+            "2024-01-01T00:00:00.Z",
+            "2024-1-01T00:00:00Z",
+        ] {
+            assert!(
+                Rfc3339::try_from(Values::String(String::from(json))).is_err(),
+                "{}",
+                json
+            );
+        }
+        assert!(Rfc3339::try_from(Values::Number(0.0)).is_err());
+    }
+}
@@ -1,6 +1,12 @@
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
+use std::str::FromStr;
 
+use crate::error::ParseError;
+use crate::parser::Parser;
 use crate::Serialize;
 
 /// Different Enums to construct an abstract JSON Hierarchy which is easier to work with and to construct
@@ -23,6 +29,14 @@ pub enum Values {
     ///     Values::Number(12.43).to_string()
     /// )
     /// ```
+    /// Negative zero keeps its sign and is written as `-0`,
+    /// which is parsed back into a negative zero
+    /// ```
+    /// use wjp::Values;
+    /// assert_eq!(Values::Number(-0.0).to_string(), "-0");
+    /// let parsed: Values = "-0".parse().unwrap();
+    /// assert!(parsed.get_number().unwrap().is_sign_negative());
+    /// ```
     Number(f64),
     /// Represents a JSON Struct
     /// ```
@@ -72,16 +86,40 @@ impl Serialize for Values {
     fn serialize(&self) -> Values {
         self.clone()
     }
+    fn json(&self) -> String {
+        self.to_json_string()
+    }
 }
 
+/// Parses a JSON String into a [`Values`] Object
+///
+/// ```
+/// use wjp::{ParseError, Values};
+/// let val: Values = "[true,null]".parse()?;
+/// assert_eq!(val, Values::Array(vec![Values::Boolean(true), Values::Null]));
+/// # Ok::<(), ParseError>(())
+/// ```
+impl FromStr for Values {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(s).parse()
+    }
+}
+
+/// Compares two [`Values`] Objects structurally.
+///
+/// Info: Numbers are compared by their value, except that `NaN` is equal to itself
+/// so that the comparison is a proper equivalence relation and [`Values`] can implement [`Eq`].
+/// A [`Number`] is never equal to a [`String`], even if the String contains the same number
+///
+/// [`Number`]: Values::Number
+/// [`String`]: Values::String
 impl PartialEq<Self> for Values {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (&Values::Null, &Values::Null) => true,
             (Values::String(a), Values::String(b)) => a == b,
-            (&Values::Number(a), &Values::String(ref b))
-            | (&Values::String(ref b), &Values::Number(a)) => a.to_string() == *b,
-            (Values::Number(a), Values::Number(b)) => a == b,
+            (Values::Number(a), Values::Number(b)) => a == b || a.is_nan() && b.is_nan(),
             (Values::Boolean(a), Values::Boolean(b)) => a == b,
             (Values::Struct(a), Values::Struct(b)) => a == b,
             (Values::Array(a), Values::Array(b)) => a == b,
@@ -90,6 +128,98 @@ impl PartialEq<Self> for Values {
     }
 }
 
+impl Eq for Values {}
+
+/// Hashes a [`Values`] Object consistently with its [`PartialEq`] impl,
+/// which allows it to be used as a key of a [`HashMap`].
+///
+/// Info: Numbers are hashed by their bit representation after mapping `-0` onto `0`
+/// and every `NaN` onto a single `NaN`. The entries of a [`Struct`] are hashed ordered by their keys
+///
+/// ```
+/// use std::collections::HashSet;
+/// use wjp::Values;
+/// let records: HashSet<Values> = ["{\"id\":1}", "{\"id\":1}", "{\"id\":2}"]
+///     .iter()
+///     .map(|json| json.parse().unwrap())
+///     .collect();
+/// assert_eq!(records.len(), 2);
+/// ```
+///
+/// [`Struct`]: Values::Struct
+impl Hash for Values {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+        match self {
+            Values::String(string) => string.hash(state),
+            Values::Number(num) if *num == 0.0 => 0u64.hash(state),
+            Values::Number(num) if num.is_nan() => f64::NAN.to_bits().hash(state),
+            Values::Number(num) => num.to_bits().hash(state),
+            Values::Struct(map) => {
+                let mut entries: Vec<(&String, &Values)> = map.iter().collect();
+                entries.sort_unstable_by_key(|(key, _)| *key);
+                entries.hash(state)
+            }
+            Values::Array(arr) => arr.hash(state),
+            Values::Null => {}
+            Values::Boolean(bool) => bool.hash(state),
+        }
+    }
+}
+
+/// Converts a [`serde_json::Value`] into a [`Values`] Object.
+///
+/// Warning: every number is converted into a [`f64`], so integers bigger than 2^53
+/// lose their precision like they do when parsing JSON with this crate
+#[cfg(feature = "serde_json")]
+impl From<serde_json::Value> for Values {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Values::Null,
+            serde_json::Value::Bool(bool) => Values::Boolean(bool),
+            serde_json::Value::Number(num) => num.as_f64().map_or(Values::Null, Values::Number),
+            serde_json::Value::String(string) => Values::String(string),
+            serde_json::Value::Array(arr) => {
+                Values::Array(arr.into_iter().map(Values::from).collect())
+            }
+            serde_json::Value::Object(map) => Values::Struct(
+                map.into_iter()
+                    .map(|(key, val)| (key, Values::from(val)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Converts a [`Values`] Object into a [`serde_json::Value`].
+/// Whole numbers that fit into an [`i64`] become integers and every other number a float.
+///
+/// Info: [`serde_json`] can't represent non-finite numbers, which are converted into `null`
+/// just like they are when this crate writes them as JSON
+#[cfg(feature = "serde_json")]
+impl From<Values> for serde_json::Value {
+    fn from(value: Values) -> Self {
+        match value {
+            Values::Null => serde_json::Value::Null,
+            Values::Boolean(bool) => serde_json::Value::Bool(bool),
+            Values::Number(num) if num.fract() == 0.0 && num.abs() < i64::MAX as f64 => {
+                serde_json::Value::from(num as i64)
+            }
+            Values::Number(num) => serde_json::Number::from_f64(num)
+                .map_or(serde_json::Value::Null, serde_json::Value::Number),
+            Values::String(string) => serde_json::Value::String(string),
+            Values::Array(arr) => {
+                serde_json::Value::Array(arr.into_iter().map(serde_json::Value::from).collect())
+            }
+            Values::Struct(map) => serde_json::Value::Object(
+                map.into_iter()
+                    .map(|(key, val)| (key, serde_json::Value::from(val)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
 impl Values {
     pub const STRING: &'static str = "string";
     pub const STRUCT: &'static str = "struct";
@@ -97,6 +227,30 @@ impl Values {
     pub const NULL: &'static str = "null";
     pub const ARRAY: &'static str = "array";
     pub const BOOLEAN: &'static str = "boolean";
+    pub const REDACTED: &'static str = "[REDACTED]";
+    /// parses every line of newline delimited JSON (NDJSON) on its own,
+    /// so that a malformed line doesn't abort the whole batch.
+    /// Empty lines are skipped and the errors report the line number starting at 1
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let lines = Values::parse_ndjson_lenient("{\"level\":\"info\"}\n{oops\n\n[1]\n");
+    /// assert_eq!(lines.len(), 3);
+    /// assert!(lines[0].is_ok() && lines[1].is_err() && lines[2].is_ok());
+    /// ```
+    pub fn parse_ndjson_lenient(ndjson: &str) -> Vec<Result<Values, ParseError>> {
+        ndjson
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                Values::from_str(line).map_err(|err| {
+                    let msg = format!("line {}: {:?}", index + 1, err);
+                    err.with_msg(msg.as_str())
+                })
+            })
+            .collect()
+    }
     /// if the provided value is a [`Struct`] it will return [`Some`]
     /// containing the inner [`Hashmap`] otherwise returns [`None`]
     ///
@@ -108,6 +262,200 @@ impl Values {
             _ => None,
         }
     }
+    /// if the provided value is a [`Struct`] containing the key it will remove
+    /// that entry and return [`Some`] containing its value without cloning it
+    /// otherwise returns [`None`]
+    ///
+    /// [`Struct`]: Values::Struct
+    pub fn take_key(&mut self, key: &str) -> Option<Values> {
+        match self {
+            Values::Struct(map) => map.remove(key),
+            _ => None,
+        }
+    }
+    /// if the provided value is a [`Array`] containing the index it will remove
+    /// that item and return [`Some`] containing it without cloning it
+    /// otherwise returns [`None`]. All items after the index are shifted to the left
+    ///
+    /// [`Array`]: Values::Array
+    pub fn take_index(&mut self, index: usize) -> Option<Values> {
+        match self {
+            Values::Array(arr) if index < arr.len() => Some(arr.remove(index)),
+            _ => None,
+        }
+    }
+    /// replaces this [`Values`] Object with [`Null`] and returns the previous value
+    /// without cloning it, like [`Option::take`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val = Values::Boolean(true);
+    /// assert_eq!(val.take(), Values::Boolean(true));
+    /// assert_eq!(val, Values::Null);
+    /// ```
+    ///
+    /// [`Null`]: Values::Null
+    pub fn take(&mut self) -> Values {
+        std::mem::replace(self, Values::Null)
+    }
+    /// if the provided value is a [`Struct`] it will return [`Some`]
+    /// containing the [`Entry`] of the provided key otherwise returns [`None`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val = Values::Struct(Default::default());
+    /// val.entry("retries").unwrap().or_insert(Values::Number(3.0));
+    /// assert_eq!(val.to_string(), "{\"retries\":3}");
+    /// ```
+    ///
+    /// [`Struct`]: Values::Struct
+    pub fn entry(&mut self, key: &str) -> Option<Entry<'_, String, Values>> {
+        match self {
+            Values::Struct(map) => Some(map.entry(String::from(key))),
+            _ => None,
+        }
+    }
+    /// if the provided value is a [`Struct`] it will return [`Some`]
+    /// containing its entries sorted by key otherwise returns [`None`]
+    ///
+    /// [`Struct`]: Values::Struct
+    pub fn sorted_entries(&self) -> Option<Vec<(&String, &Values)>> {
+        match self {
+            Values::Struct(map) => {
+                let mut entries: Vec<(&String, &Values)> = map.iter().collect();
+                entries.sort_unstable_by_key(|(key, _)| *key);
+                Some(entries)
+            }
+            _ => None,
+        }
+    }
+    /// if the provided value is a [`Struct`] containing the key it will return the value
+    /// of that key otherwise returns the provided default
+    ///
+    /// [`Struct`]: Values::Struct
+    pub fn get_or<'a>(&'a self, key: &str, default: &'a Values) -> &'a Values {
+        match self {
+            Values::Struct(map) => map.get(key).unwrap_or(default),
+            _ => default,
+        }
+    }
+    /// if the provided value is a [`Struct`] containing the key with a [`Number`]
+    /// it will return that number otherwise returns the provided default
+    ///
+    /// [`Struct`]: Values::Struct
+    /// [`Number`]: Values::Number
+    pub fn get_number_or(&self, key: &str, default: f64) -> f64 {
+        self.get_or(key, &Values::Null)
+            .get_number()
+            .unwrap_or(default)
+    }
+    /// get a reference to a nested value by a path of keys separated by dots.
+    /// Segments are used as keys of a [`Struct`] or as indices of an [`Array`].
+    /// An empty path returns the value itself.
+    ///
+    /// Info: Keys containing a dot can't be reached with this method
+    ///
+    /// ```
+    /// use wjp::{map, Serialize, Values};
+    /// let user = Values::Struct(map!(("name", "Merlin")));
+    /// let val = Values::Struct(map!(("users", &vec![user])));
+    /// assert_eq!(
+    ///     val.get_path("users.0.name"),
+    ///     Some(&Values::String(String::from("Merlin")))
+    /// );
+    /// ```
+    ///
+    /// [`Struct`]: Values::Struct
+    /// [`Array`]: Values::Array
+    pub fn get_path(&self, path: &str) -> Option<&Values> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('.')
+            .try_fold(self, |current, segment| match current {
+                Values::Struct(map) => map.get(segment),
+                Values::Array(arr) => usize::from_str(segment).ok().and_then(|i| arr.get(i)),
+                _ => None,
+            })
+    }
+    /// collects references to every value matching the provided path.
+    /// The path uses the same syntax as [`get_path`] with one addition:
+    /// the segment `*` matches every element of an [`Array`] and every value of a [`Struct`].
+    /// Values of a Struct are visited in the order of their keys.
+    /// Segments that don't match anything are skipped, so the result may be empty
+    ///
+    /// Info: Only whole segments can be `*`, patterns like `user*` are treated as plain keys
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"users\":[{\"email\":\"merlin@camelot\"},{\"email\":\"morgana@avalon\"}]}"
+    ///     .parse()
+    ///     .unwrap();
+    /// let emails: Vec<&str> = val
+    ///     .select("users.*.email")
+    ///     .into_iter()
+    ///     .filter_map(Values::as_str)
+    ///     .collect();
+    /// assert_eq!(emails, vec!["merlin@camelot", "morgana@avalon"]);
+    /// ```
+    ///
+    /// [`get_path`]: Values::get_path
+    /// [`Struct`]: Values::Struct
+    /// [`Array`]: Values::Array
+    pub fn select(&self, path: &str) -> Vec<&Values> {
+        if path.is_empty() {
+            return vec![self];
+        }
+        path.split('.').fold(vec![self], |current, segment| {
+            current
+                .into_iter()
+                .flat_map(|val| match (val, segment) {
+                    (Values::Array(arr), "*") => arr.iter().collect(),
+                    (Values::Struct(map), "*") => {
+                        let mut entries: Vec<(&String, &Values)> = map.iter().collect();
+                        entries.sort_by_key(|(key, _)| *key);
+                        entries.into_iter().map(|(_, val)| val).collect()
+                    }
+                    (Values::Struct(map), key) => map.get(key).into_iter().collect(),
+                    (Values::Array(arr), index) => usize::from_str(index)
+                        .ok()
+                        .and_then(|i| arr.get(i))
+                        .into_iter()
+                        .collect(),
+                    _ => Vec::new(),
+                })
+                .collect()
+        })
+    }
+    /// replaces the value at the provided path of Struct keys and Array indices
+    /// and returns true. If the path doesn't exist nothing is created and false is returned.
+    /// Unlike [`get_path`] the segments are passed separately, so keys may contain dots
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val: Values = "{\"users\":[{\"name\":\"Merlin\"}]}".parse().unwrap();
+    /// assert!(val.replace_at(&["users", "0", "name"], Values::Null));
+    /// assert_eq!(val.to_string(), "{\"users\":[{\"name\":null}]}");
+    /// assert!(!val.replace_at(&["users", "1"], Values::Null));
+    /// ```
+    ///
+    /// [`get_path`]: Values::get_path
+    pub fn replace_at(&mut self, path: &[&str], value: Values) -> bool {
+        let target = path
+            .iter()
+            .try_fold(self, |current, segment| match current {
+                Values::Struct(map) => map.get_mut(*segment),
+                Values::Array(arr) => usize::from_str(segment).ok().and_then(|i| arr.get_mut(i)),
+                _ => None,
+            });
+        match target {
+            Some(target) => {
+                *target = value;
+                true
+            }
+            None => false,
+        }
+    }
     /// if the provided value is a [`Boolean`] it will return [`Some`]
     /// containing the inner [`bool`] otherwise returns [`None`]
     ///
@@ -118,6 +466,30 @@ impl Values {
             _ => None,
         }
     }
+    /// converts a [`Boolean`] or the [`Number`]s `0` and `1` into a [`bool`].
+    /// Every other value results in a [`ParseError`]. Unlike [`get_bool`]
+    /// this accepts booleans exported as integers, e.g. by some databases
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert_eq!(Values::Number(1.0).coerce_bool_int(), Ok(true));
+    /// assert!(Values::Number(2.0).coerce_bool_int().is_err());
+    /// ```
+    ///
+    /// [`Boolean`]: Values::Boolean
+    /// [`Number`]: Values::Number
+    /// [`get_bool`]: Values::get_bool
+    pub fn coerce_bool_int(&self) -> Result<bool, ParseError> {
+        match self {
+            Values::Boolean(bool) => Ok(*bool),
+            Values::Number(num) if *num == 0.0 => Ok(false),
+            Values::Number(num) if *num == 1.0 => Ok(true),
+            other => Err(ParseError::from(format!(
+                "value {} is not a boolean",
+                other
+            ))),
+        }
+    }
     /// if the provided value is a [`String`] it will return [`Some`]
     /// containing the inner [`str`] otherwise returns [`None`]
     ///
@@ -129,6 +501,42 @@ impl Values {
             _ => None,
         }
     }
+    /// if the provided value is a [`String`] it will return [`Some`]
+    /// containing the borrowed [`str`] otherwise returns [`None`].
+    /// Unlike [`get_string`] this doesn't clone the inner [`String`]
+    ///
+    /// [`String`]: Values::String
+    /// [`get_string`]: Values::get_string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Values::String(string) => Some(string.as_str()),
+            _ => None,
+        }
+    }
+    /// converts a [`String`], [`Number`] or [`Boolean`] into its text.
+    /// Unlike [`get_string`] this also accepts numbers and booleans,
+    /// every other value results in a [`ParseError`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert_eq!(Values::Number(42.0).to_string_value(), Ok(String::from("42")));
+    /// assert!(Values::Number(42.0).get_string().is_none());
+    /// ```
+    ///
+    /// [`String`]: Values::String
+    /// [`Number`]: Values::Number
+    /// [`Boolean`]: Values::Boolean
+    /// [`get_string`]: Values::get_string
+    pub fn to_string_value(&self) -> Result<String, ParseError> {
+        match self {
+            Values::String(string) => Ok(string.clone()),
+            Values::Number(_) | Values::Boolean(_) => Ok(self.to_string()),
+            other => Err(ParseError::from(format!(
+                "expected a string, number or boolean but found {}",
+                other.get_type_as_string()
+            ))),
+        }
+    }
     /// if the provided value is a [`Number`] it will return [`Some`]
     /// containing the inner [`f64`] otherwise returns [`None`]
     ///
@@ -139,6 +547,65 @@ impl Values {
             _ => None,
         }
     }
+    /// if the provided value is a [`Number`] or a [`String`] containing a number
+    /// it will return [`Some`] containing the [`f64`] otherwise returns [`None`]
+    ///
+    /// Warning: this conversion is lossy for integers that are bigger than 2^53
+    /// because every number is read as a [`f64`]
+    ///
+    /// [`Number`]: Values::Number
+    /// [`String`]: Values::String
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Values::Number(num) => Some(*num),
+            Values::String(string) => f64::from_str(string.trim()).ok(),
+            _ => None,
+        }
+    }
+    /// converts the value into T.
+    /// This is the same as `T::try_from(value)` but reads better at the end of a chain
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"level\":42}".parse().unwrap();
+    /// let level = val.get_path("level").cloned().unwrap_or_default().into_type::<u8>();
+    /// assert_eq!(level, Ok(42));
+    /// ```
+    pub fn into_type<T: TryFrom<Values, Error = ParseError>>(self) -> Result<T, ParseError> {
+        T::try_from(self)
+    }
+    /// converts `null` into [`None`] and every other value into [`Some`] of T
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert_eq!(Values::Null.into_option::<bool>(), Ok(None));
+    /// assert_eq!(Values::Boolean(true).into_option::<bool>(), Ok(Some(true)));
+    /// ```
+    pub fn into_option<T: TryFrom<Values, Error = ParseError>>(
+        self,
+    ) -> Result<Option<T>, ParseError> {
+        match self {
+            Values::Null => Ok(None),
+            other => T::try_from(other).map(Some),
+        }
+    }
+    /// if the provided value is a [`String`] containing a decimal number
+    /// it will return [`Some`] containing the exact [`Decimal`] otherwise returns [`None`].
+    ///
+    /// Info: Only string-encoded decimals like `"0.30"` are read. A [`Number`] was already
+    /// rounded to a [`f64`] when it was parsed, so it can't be turned into an exact [`Decimal`]
+    /// and results in [`None`] as well
+    ///
+    /// [`String`]: Values::String
+    /// [`Number`]: Values::Number
+    /// [`Decimal`]: rust_decimal::Decimal
+    #[cfg(feature = "decimal")]
+    pub fn get_string_decimal(&self) -> Option<rust_decimal::Decimal> {
+        match self {
+            Values::String(string) => rust_decimal::Decimal::from_str(string.as_str()).ok(),
+            _ => None,
+        }
+    }
     /// if the provided value is a [`Array`] it will return [`Some`]
     /// containing the inner [`Vec<Values>`] otherwise returns [`None`]
     ///
@@ -149,6 +616,75 @@ impl Values {
             _ => None,
         }
     }
+    /// if the provided value is a [`Array`] of numbers fitting into a [`u8`] it will return [`Ok`]
+    /// containing the bytes. With the `base64` feature enabled a [`String`] is decoded as standard Base64.
+    /// Every other value results in a [`ParseError`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let bytes: Values = "[104, 105]".parse().unwrap();
+    /// assert_eq!(bytes.get_bytes(), Ok(vec![104, 105]));
+    /// ```
+    ///
+    /// [`Array`]: Values::Array
+    /// [`String`]: Values::String
+    pub fn get_bytes(&self) -> Result<Vec<u8>, ParseError> {
+        match self {
+            Values::Array(arr) => arr.iter().map(|item| u8::try_from(item.clone())).collect(),
+            #[cfg(feature = "base64")]
+            Values::String(string) => {
+                use base64::Engine;
+                base64::engine::general_purpose::STANDARD
+                    .decode(string)
+                    .map_err(|err| ParseError::new().with_msg(err.to_string().as_str()))
+            }
+            other => Err(ParseError::from(format!(
+                "expected {}, found {}",
+                if cfg!(feature = "base64") {
+                    "an Array of bytes or a Base64 String"
+                } else {
+                    "an Array of bytes"
+                },
+                other.get_type_as_string()
+            ))),
+        }
+    }
+    /// if the provided value is a [`Array`] or a [`Struct`] whose keys are exactly
+    /// `"0"`, `"1"`, ... without any gaps it will return [`Some`] containing the items
+    /// ordered by their index otherwise returns [`None`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"1\":\"b\",\"0\":\"a\"}".parse().unwrap();
+    /// assert_eq!(val.coerce_array().unwrap(), "[\"a\",\"b\"]".parse::<Values>().unwrap().get_list());
+    /// ```
+    ///
+    /// [`Array`]: Values::Array
+    /// [`Struct`]: Values::Struct
+    pub fn coerce_array(&self) -> Option<Vec<Values>> {
+        match self {
+            Values::Array(arr) => Some(arr.to_vec()),
+            Values::Struct(map) => (0..map.len())
+                .map(|index| map.get(index.to_string().as_str()).cloned())
+                .collect(),
+            _ => None,
+        }
+    }
+    /// if the provided value is a [`Array`] with exactly `len` items it will return [`Ok`]
+    /// containing the inner items otherwise returns a [`ParseError`] describing the mismatch
+    ///
+    /// [`Array`]: Values::Array
+    pub fn as_array_of_len(&self, len: usize) -> Result<&[Values], ParseError> {
+        let found = match self {
+            Values::Array(arr) if arr.len() == len => return Ok(arr),
+            Values::Array(arr) => arr.len().to_string(),
+            other => other.get_type_as_string().to_string(),
+        };
+        Err(ParseError::from(format!(
+            "expected an array of length {} but found {}",
+            len, found
+        )))
+    }
     /// if the provided value is a [`Array`] it will return it
     /// otherwise an empty Vec
     ///
@@ -166,7 +702,7 @@ impl Values {
     ///[`NULL`]: Self::NULL
     ///[`ARRAY`]: Self::ARRAY
     ///[`BOOLEAN`]: Self::BOOLEAN
-    pub fn get_type_as_string(&self) -> &str {
+    pub fn get_type_as_string(&self) -> &'static str {
         match self {
             Values::String(_) => Self::STRING,
             Values::Number(_) => Self::NUMBER,
@@ -176,6 +712,62 @@ impl Values {
             Values::Boolean(_) => Self::BOOLEAN,
         }
     }
+    /// returns the [`Default`] of T if the provided value is [`Null`]
+    /// otherwise returns a [`ParseError`]. Used by [`impl_unit_serialize`]
+    ///
+    /// [`Null`]: Values::Null
+    /// [`impl_unit_serialize`]: crate::impl_unit_serialize
+    #[doc(hidden)]
+    pub fn unit_from<T: Default>(value: Values) -> Result<T, ParseError> {
+        match value {
+            Values::Null => Ok(T::default()),
+            other => Err(ParseError::from(format!(
+                "expected null but found {}",
+                other.get_type_as_string()
+            ))),
+        }
+    }
+    /// if the provided value is a non-empty [`Array`] whose items all have the same type
+    /// it will return [`Some`] containing the name of that type like [`get_type_as_string`]
+    /// otherwise returns [`None`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let numbers: Values = "[1,2.5,3]".parse().unwrap();
+    /// assert_eq!(numbers.array_element_type(), Some(Values::NUMBER));
+    /// let mixed: Values = "[1,\"2\"]".parse().unwrap();
+    /// assert_eq!(mixed.array_element_type(), None);
+    /// ```
+    ///
+    /// [`Array`]: Values::Array
+    /// [`get_type_as_string`]: Values::get_type_as_string
+    pub fn array_element_type(&self) -> Option<&'static str> {
+        let Values::Array(arr) = self else {
+            return None;
+        };
+        let first = arr.first()?.get_type_as_string();
+        arr.iter()
+            .all(|item| item.get_type_as_string() == first)
+            .then_some(first)
+    }
+    /// returns true if the provided Value would be truthy in JavaScript.
+    /// Only `false`, `0`, `-0`, `NaN`, `""` and `null` are falsy.
+    /// Like in JavaScript empty Arrays and Structs are truthy
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert!(!Values::String(String::new()).is_truthy());
+    /// assert!(Values::Array(vec![]).is_truthy());
+    /// ```
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Values::Boolean(bool) => *bool,
+            Values::Number(num) => *num != 0.0 && !num.is_nan(),
+            Values::String(string) => !string.is_empty(),
+            Values::Null => false,
+            Values::Struct(_) | Values::Array(_) => true,
+        }
+    }
     /// returns true if the provided Value is [`Boolean`]
     ///
     /// [`Boolean`]: Values::Boolean
@@ -200,6 +792,35 @@ impl Values {
     pub fn is_number(&self) -> bool {
         self.get_type_as_string().eq(Self::NUMBER)
     }
+    /// returns true if the provided Value is a finite [`Number`] without a fractional part.
+    ///
+    /// Info: Every Number is stored as a [`f64`], so `2.0` and `2` are both integers
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert!(Values::Number(2.0).is_integer());
+    /// assert!(!Values::Number(1.5).is_integer());
+    /// assert!(!Values::String(String::from("2")).is_integer());
+    /// ```
+    ///
+    /// [`Number`]: Values::Number
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Values::Number(num) if num.is_finite() && num.fract() == 0.0)
+    }
+    /// if the provided value is an integer [`Number`] that fits into an [`i128`]
+    /// it will return [`Some`] containing the integer otherwise returns [`None`]
+    ///
+    /// [`Number`]: Values::Number
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Values::Number(num)
+                if self.is_integer() && (i128::MIN as f64..i128::MAX as f64).contains(num) =>
+            {
+                Some(*num as i128)
+            }
+            _ => None,
+        }
+    }
     /// returns true if the provided Value is [`Struct`]
     ///
     /// [`Struct`]: Values::Struct
@@ -216,125 +837,1833 @@ impl Values {
 
 impl Display for Values {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Values::String(string) => write!(f, "\"{}\"", string.replace('"', "\\\"")),
-            Values::Number(number) => write!(f, "{}", number),
-            Values::Struct(r#struct) => {
-                write!(f, "{{")?;
-                let mut first = true;
-                for (key, val) in r#struct {
-                    if first {
-                        write!(f, "\"{}\":{}", key, val)?;
-                        first = false;
-                    } else {
-                        write!(f, ",\"{}\":{}", key, val)?
-                    }
-                }
-                write!(f, "}}")
-            }
-            Values::Array(arr) => {
-                write!(f, "[")?;
-                let mut first = true;
-                for item in arr {
-                    if first {
-                        write!(f, "{}", item)?;
-                        first = false;
-                    } else {
-                        write!(f, ",{}", item)?;
-                    }
-                }
-                write!(f, "]")
-            }
-            Values::Null => write!(f, "{}", Self::NULL),
-            Values::Boolean(bool) => write!(f, "{}", bool),
-        }
+        self.write_json(f, false)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::serializer::Serialize;
-    use crate::values::Values;
-    use crate::{map, FALSE, NULL, TRUE};
+/// parses the provided JSON and writes it again without any whitespace
+///
+/// ```
+/// assert_eq!(wjp::minify("[ 1,\n  true ]"), Ok(String::from("[1,true]")));
+/// ```
+pub fn minify(json: &str) -> Result<String, ParseError> {
+    Values::from_str(json).map(|val| val.to_json_string())
+}
 
-    #[test]
-    pub fn display_on_bool_true() {
-        let bool = TRUE;
-        assert_eq!(bool.to_string(), "true")
-    }
+/// parses the provided JSON and writes it again like [`Values::to_pretty_string`]
+///
+/// ```
+/// assert_eq!(wjp::prettify("[1,true]", 2), Ok(String::from("[\n  1,\n  true\n]")));
+/// ```
+pub fn prettify(json: &str, indent: usize) -> Result<String, ParseError> {
+    Values::from_str(json).map(|val| val.to_pretty_string(indent))
+}
 
-    #[test]
+struct Extended<'a>(&'a Values);
+
+// Counts the bytes written to it instead of storing them
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+impl Display for Extended<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.write_json(f, true)
+    }
+}
+
+impl Values {
+    /// serializes this [`Values`] Object like its [`Display`] impl, but
+    /// writes non-finite numbers as `Infinity`, `-Infinity` and `NaN` instead of `null`.
+    /// This isn't valid JSON and can only be parsed back with [`ParseOptions::with_extended_numbers`]
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// assert_eq!(Values::Number(f64::NAN).to_string(), "null");
+    /// assert_eq!(Values::Number(f64::NAN).to_extended_string(), "NaN");
+    /// ```
+    ///
+    /// [`ParseOptions::with_extended_numbers`]: crate::ParseOptions::with_extended_numbers
+    pub fn to_extended_string(&self) -> String {
+        Extended(self).to_string()
+    }
+    // the same as to_string, but writes into the String directly instead of through a Formatter
+    pub(crate) fn to_json_string(&self) -> String {
+        let mut json = String::new();
+        // writing into a String never fails
+        let _ = self.write_json(&mut json, false);
+        json
+    }
+    /// serializes this [`Values`] Object like its [`Display`] impl, but puts every item of
+    /// a Struct or Array on its own line, indented by `indent` spaces per level.
+    /// Empty Structs and Arrays are written as `{}` and `[]`
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"spells\":[\"lumos\"]}".parse().unwrap();
+    /// assert_eq!(val.to_pretty_string(2), "{\n  \"spells\": [\n    \"lumos\"\n  ]\n}");
+    /// ```
+    pub fn to_pretty_string(&self, indent: usize) -> String {
+        let mut json = String::new();
+        self.write_pretty(&mut json, indent, 0);
+        json
+    }
+    fn write_pretty(&self, json: &mut String, indent: usize, depth: usize) {
+        let newline = |json: &mut String, depth: usize| {
+            json.push('\n');
+            json.push_str(" ".repeat(indent * depth).as_str());
+        };
+        match self {
+            Values::Struct(map) if !map.is_empty() => {
+                json.push('{');
+                for (i, (key, val)) in map.iter().enumerate() {
+                    if i != 0 {
+                        json.push(',');
+                    }
+                    newline(json, depth + 1);
+                    write_canonical_string(key, json);
+                    json.push_str(": ");
+                    val.write_pretty(json, indent, depth + 1);
+                }
+                newline(json, depth);
+                json.push('}');
+            }
+            Values::Array(arr) if !arr.is_empty() => {
+                json.push('[');
+                for (i, item) in arr.iter().enumerate() {
+                    if i != 0 {
+                        json.push(',');
+                    }
+                    newline(json, depth + 1);
+                    item.write_pretty(json, indent, depth + 1);
+                }
+                newline(json, depth);
+                json.push(']');
+            }
+            other => json.push_str(other.to_string().as_str()),
+        }
+    }
+    /// applies the provided function to every [`String`], [`Number`], [`Boolean`] and [`Null`]
+    /// inside of this [`Values`] Object while leaving the Structs and Arrays around them intact
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val = Values::Array(vec![Values::Number(1.26), Values::Number(2.71)]);
+    /// val.map_leaves(|leaf| {
+    ///     if let Values::Number(num) = leaf {
+    ///         *num = num.round()
+    ///     }
+    /// });
+    /// assert_eq!(val.to_string(), "[1,3]");
+    /// ```
+    ///
+    /// [`String`]: Values::String
+    /// [`Number`]: Values::Number
+    /// [`Boolean`]: Values::Boolean
+    /// [`Null`]: Values::Null
+    pub fn map_leaves<F: FnMut(&mut Values)>(&mut self, mut f: F) {
+        let mut stack = vec![self];
+        while let Some(val) = stack.pop() {
+            match val {
+                Values::Struct(map) => stack.extend(map.values_mut()),
+                Values::Array(arr) => stack.extend(arr.iter_mut()),
+                leaf => f(leaf),
+            }
+        }
+    }
+    /// normalizes every [`Number`] inside of this [`Values`] Object, so that numbers that compare
+    /// equal are also written equally. Since every Number is a [`f64`], `2.0` and `2` already are
+    /// the same value and this only replaces negative zero, which is written as `-0`, with `0`
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val: Values = "[2.0,-0,-0.5]".parse().unwrap();
+    /// val.canonicalize_numbers();
+    /// assert_eq!(val.to_string(), "[2,0,-0.5]");
+    /// ```
+    ///
+    /// [`Number`]: Values::Number
+    pub fn canonicalize_numbers(&mut self) {
+        self.map_leaves(|leaf| {
+            if let Values::Number(num) = leaf {
+                if *num == 0.0 {
+                    *num = 0.0
+                }
+            }
+        })
+    }
+    /// estimates the amount of bytes this [`Values`] Object takes up when it is written as JSON.
+    /// The structure is counted exactly, Strings and keys without their escapes
+    /// and every [`Number`] is assumed to take up 8 bytes
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"name\":\"Merlin\",\"tags\":[true,null]}".parse().unwrap();
+    /// assert_eq!(val.estimated_size(), val.to_string().len());
+    /// ```
+    ///
+    /// [`Number`]: Values::Number
+    pub fn estimated_size(&self) -> usize {
+        let mut size = 0;
+        let mut stack = vec![self];
+        while let Some(val) = stack.pop() {
+            size += match val {
+                Values::String(string) => string.len() + 2,
+                Values::Number(_) => 8,
+                Values::Struct(map) => {
+                    let mut keys = 0;
+                    for (key, val) in map {
+                        keys += key.len() + 3;
+                        stack.push(val);
+                    }
+                    keys + map.len().max(1) + 1
+                }
+                Values::Array(arr) => {
+                    stack.extend(arr.iter());
+                    arr.len().max(1) + 1
+                }
+                Values::Null => 4,
+                Values::Boolean(true) => 4,
+                Values::Boolean(false) => 5,
+            }
+        }
+        size
+    }
+    /// counts how many values of every type are inside of this [`Values`] Object,
+    /// including itself. The types are named like in [`get_type_as_string`]
+    /// and types that don't appear are missing in the result
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "[1,2,{\"a\":null}]".parse().unwrap();
+    /// let histogram = val.type_histogram();
+    /// assert_eq!(histogram[Values::NUMBER], 2);
+    /// assert_eq!(histogram[Values::ARRAY], 1);
+    /// assert!(!histogram.contains_key(Values::STRING));
+    /// ```
+    ///
+    /// [`get_type_as_string`]: Values::get_type_as_string
+    pub fn type_histogram(&self) -> HashMap<&'static str, usize> {
+        let mut histogram = HashMap::new();
+        let mut stack = vec![self];
+        while let Some(val) = stack.pop() {
+            *histogram.entry(val.get_type_as_string()).or_insert(0) += 1;
+            match val {
+                Values::Struct(map) => stack.extend(map.values()),
+                Values::Array(arr) => stack.extend(arr.iter()),
+                _ => {}
+            }
+        }
+        histogram
+    }
+    /// returns the exact amount of bytes of the compact JSON of this [`Values`] Object,
+    /// which is the same as `val.to_string().len()` but without building the String.
+    /// Unlike [`estimated_size`] this walks through the whole formatting including escapes
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let val: Values = "{\"spell\":\"lumos\\n\",\"mana\":[1.5,20]}".parse().unwrap();
+    /// assert_eq!(val.serialized_len(), val.to_string().len());
+    /// ```
+    ///
+    /// [`estimated_size`]: Values::estimated_size
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = ByteCounter(0);
+        // ByteCounter never fails, so the only possible error comes from a broken Display impl
+        let _ = write!(counter, "{}", self);
+        counter.0
+    }
+    /// merges the other [`Values`] Object into this one like [`merge_with`]
+    /// while replacing Arrays as a whole
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut config: Values = "{\"debug\":false,\"db\":{\"host\":\"localhost\",\"port\":5432}}".parse().unwrap();
+    /// config.merge("{\"debug\":true,\"db\":{\"port\":6543}}".parse().unwrap());
+    /// let merged: Values = "{\"debug\":true,\"db\":{\"host\":\"localhost\",\"port\":6543}}".parse().unwrap();
+    /// assert_eq!(config, merged);
+    /// ```
+    ///
+    /// [`merge_with`]: Values::merge_with
+    pub fn merge(&mut self, other: Values) {
+        self.merge_with(other, ArrayMerge::Replace)
+    }
+    /// merges the other [`Values`] Object into this one. The entries of two Structs are merged
+    /// recursively, two Arrays are combined according to the [`ArrayMerge`] strategy
+    /// and every other value is replaced by the other value
+    pub fn merge_with(&mut self, other: Values, array_strategy: ArrayMerge) {
+        match (self, other) {
+            (Values::Struct(map), Values::Struct(other)) => {
+                for (key, val) in other {
+                    match map.entry(key) {
+                        Entry::Occupied(mut entry) => {
+                            entry.get_mut().merge_with(val, array_strategy)
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(val);
+                        }
+                    }
+                }
+            }
+            (Values::Array(arr), Values::Array(other)) => match array_strategy {
+                ArrayMerge::Replace => *arr = other,
+                ArrayMerge::Concat => arr.extend(other),
+                ArrayMerge::ByIndex => {
+                    let mut other = other.into_iter();
+                    for (item, val) in arr.iter_mut().zip(other.by_ref()) {
+                        item.merge_with(val, array_strategy)
+                    }
+                    arr.extend(other)
+                }
+            },
+            (this, other) => *this = other,
+        }
+    }
+    /// returns a copy of this [`Values`] Object in which the value of every Struct entry
+    /// with one of the provided keys is replaced by `"[REDACTED]"`, no matter how deeply nested.
+    /// Useful for logging payloads that contain secrets
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use wjp::Values;
+    /// let login: Values = "{\"user\":\"merlin\",\"password\":\"abracadabra\"}".parse().unwrap();
+    /// let redacted = login.redact(&HashSet::from(["password"]));
+    /// assert_eq!(redacted.get_path("password").and_then(Values::as_str), Some("[REDACTED]"));
+    /// ```
+    pub fn redact(&self, keys: &HashSet<&str>) -> Values {
+        self.redact_with(keys, Redaction::Replace)
+    }
+    /// returns a copy of this [`Values`] Object in which every Struct entry with one of
+    /// the provided keys is redacted according to the [`Redaction`] strategy
+    pub fn redact_with(&self, keys: &HashSet<&str>, redaction: Redaction) -> Values {
+        match self {
+            Values::Struct(map) => Values::Struct(
+                map.iter()
+                    .filter_map(|(key, val)| match redaction {
+                        _ if !keys.contains(key.as_str()) => {
+                            Some((key.clone(), val.redact_with(keys, redaction)))
+                        }
+                        Redaction::Replace => {
+                            Some((key.clone(), Values::String(String::from(Self::REDACTED))))
+                        }
+                        Redaction::Remove => None,
+                    })
+                    .collect(),
+            ),
+            Values::Array(arr) => Values::Array(
+                arr.iter()
+                    .map(|val| val.redact_with(keys, redaction))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+    /// fills in the keys of the defaults that are missing in this [`Values`] Object.
+    /// Unlike [`merge`] the existing keys win, even if they are `null`.
+    /// Structs on both sides are filled in recursively, every other value is kept as it is
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut config: Values = "{\"db\":{\"port\":6543}}".parse().unwrap();
+    /// config.apply_defaults(&"{\"debug\":false,\"db\":{\"host\":\"localhost\",\"port\":5432}}".parse().unwrap());
+    /// let filled: Values = "{\"debug\":false,\"db\":{\"host\":\"localhost\",\"port\":6543}}".parse().unwrap();
+    /// assert_eq!(config, filled);
+    /// ```
+    ///
+    /// [`merge`]: Values::merge
+    pub fn apply_defaults(&mut self, defaults: &Values) {
+        if let (Values::Struct(map), Values::Struct(defaults)) = (self, defaults) {
+            for (key, default) in defaults {
+                match map.get_mut(key) {
+                    Some(val) => val.apply_defaults(default),
+                    None => {
+                        map.insert(key.clone(), default.clone());
+                    }
+                }
+            }
+        }
+    }
+    /// recursively removes every Struct key whose value is an empty Struct or an empty Array
+    /// and with `prune_nulls` also every key whose value is [`Null`].
+    /// The removal happens bottom-up, so a Struct that only becomes empty through pruning is removed as well.
+    /// Items of Arrays are never removed
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let mut val: Values = "{\"a\":{\"b\":[]},\"c\":null,\"d\":1}".parse().unwrap();
+    /// val.prune_empty(true);
+    /// assert_eq!(val.to_string(), "{\"d\":1}");
+    /// ```
+    ///
+    /// [`Null`]: Values::Null
+    pub fn prune_empty(&mut self, prune_nulls: bool) {
+        match self {
+            Values::Struct(map) => {
+                map.retain(|_, val| {
+                    val.prune_empty(prune_nulls);
+                    !(val.is_empty_container() || prune_nulls && val.is_null())
+                });
+            }
+            Values::Array(arr) => arr.iter_mut().for_each(|val| val.prune_empty(prune_nulls)),
+            _ => {}
+        }
+    }
+    /// recursively removes every Struct key whose value is [`Null`] while keeping empty
+    /// Structs and Arrays, unlike [`prune_empty`]. Items of Arrays are never removed.
+    /// Useful for sparse update payloads built from maps of [`Option`]s
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use wjp::{Serialize, Values};
+    /// let update = HashMap::from([("name", Some("Merlin")), ("title", None)]);
+    /// let mut val = update.serialize();
+    /// val.strip_null_entries();
+    /// assert_eq!(val.to_string(), "{\"name\":\"Merlin\"}");
+    /// ```
+    ///
+    /// [`Null`]: Values::Null
+    /// [`prune_empty`]: Values::prune_empty
+    pub fn strip_null_entries(&mut self) {
+        let mut stack = vec![self];
+        while let Some(val) = stack.pop() {
+            match val {
+                Values::Struct(map) => {
+                    map.retain(|_, val| !val.is_null());
+                    stack.extend(map.values_mut());
+                }
+                Values::Array(arr) => stack.extend(arr.iter_mut()),
+                _ => {}
+            }
+        }
+    }
+    fn is_empty_container(&self) -> bool {
+        match self {
+            Values::Struct(map) => map.is_empty(),
+            Values::Array(arr) => arr.is_empty(),
+            _ => false,
+        }
+    }
+    /// walks through this [`Values`] Object depth first and calls the [`ValueVisitor`]
+    /// for every value inside of it. The entries of a Struct are visited ordered by their keys.
+    /// The traversal doesn't use recursion, so deeply nested values can't overflow the stack
+    ///
+    /// ```
+    /// use wjp::{ValueVisitor, Values};
+    /// struct Count(usize);
+    /// impl ValueVisitor for Count {
+    ///     fn scalar(&mut self, _path: &str, _value: &Values) {
+    ///         self.0 += 1
+    ///     }
+    /// }
+    /// let val: Values = "{\"a\":[1,2],\"b\":{\"c\":null}}".parse().unwrap();
+    /// let mut count = Count(0);
+    /// val.walk(&mut count);
+    /// assert_eq!(count.0, 3);
+    /// ```
+    pub fn walk<V: ValueVisitor>(&self, visitor: &mut V) {
+        let mut stack = vec![Visit::Value(String::new(), self)];
+        while let Some(visit) = stack.pop() {
+            let (path, value) = match visit {
+                Visit::Leave(path, value) => {
+                    visitor.leave(path.as_str(), value);
+                    continue;
+                }
+                Visit::Value(path, value) => (path, value),
+            };
+            match value {
+                Values::Struct(map) => {
+                    visitor.enter(path.as_str(), value);
+                    let mut entries: Vec<(&String, &Values)> = map.iter().collect();
+                    entries.sort_unstable_by_key(|(key, _)| *key);
+                    stack.push(Visit::Leave(path.clone(), value));
+                    for (key, val) in entries.into_iter().rev() {
+                        stack.push(Visit::Value(
+                            format!("{}/{}", path, escape_pointer(key)),
+                            val,
+                        ));
+                    }
+                }
+                Values::Array(arr) => {
+                    visitor.enter(path.as_str(), value);
+                    stack.push(Visit::Leave(path.clone(), value));
+                    for (i, val) in arr.iter().enumerate().rev() {
+                        stack.push(Visit::Value(format!("{}/{}", path, i), val));
+                    }
+                }
+                _ => visitor.scalar(path.as_str(), value),
+            }
+        }
+    }
+    /// infers a simple description of the structure of this [`Values`] Object.
+    /// Every scalar is replaced by its type from [`get_type_as_string`], Structs keep their keys
+    /// and an Array is described by a single item: the schema of its first item,
+    /// `"mixed"` if the items have different schemas or nothing if it is empty
+    ///
+    /// ```
+    /// use wjp::Values;
+    /// let sample: Values = "{\"name\":\"Merlin\",\"age\":760,\"tags\":[\"wizard\"]}"
+    ///     .parse()
+    ///     .unwrap();
+    /// let schema: Values = "{\"name\":\"string\",\"age\":\"number\",\"tags\":[\"string\"]}"
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(sample.infer_schema(), schema);
+    /// ```
+    ///
+    /// [`get_type_as_string`]: Values::get_type_as_string
+    pub fn infer_schema(&self) -> Values {
+        match self {
+            Values::Struct(map) => Values::Struct(
+                map.iter()
+                    .map(|(key, val)| (key.clone(), val.infer_schema()))
+                    .collect(),
+            ),
+            Values::Array(arr) => {
+                let mut schemas = arr.iter().map(Values::infer_schema);
+                match schemas.next() {
+                    None => Values::Array(Vec::new()),
+                    Some(first) if schemas.all(|schema| schema == first) => {
+                        Values::Array(vec![first])
+                    }
+                    Some(_) => Values::Array(vec![Values::String(String::from("mixed"))]),
+                }
+            }
+            scalar => Values::String(String::from(scalar.get_type_as_string())),
+        }
+    }
+    /// describes every difference between this [`Values`] Object as the expected value
+    /// and the other Object as the found value in a human readable way.
+    /// The locations are written like JSON Pointers and an empty list means both are equal
+    ///
+    /// ```
+    /// use wjp::{map, Serialize, Values};
+    /// let expected = Values::Struct(map!(("a", &1), ("b", &true)));
+    /// let found = Values::Struct(map!(("a", "x")));
+    /// let mut diff = expected.describe_diff(&found);
+    /// diff.sort();
+    /// assert_eq!(
+    ///     diff,
+    ///     vec!["at /a: expected Number(1), found String(\"x\")", "missing key /b"]
+    /// );
+    /// ```
+    pub fn describe_diff(&self, other: &Values) -> Vec<String> {
+        let mut diffs = Vec::new();
+        let mut stack = vec![(String::new(), self, other)];
+        while let Some((path, expected, found)) = stack.pop() {
+            match (expected, found) {
+                (Values::Struct(a), Values::Struct(b)) => {
+                    for (key, val) in a {
+                        let child = format!("{}/{}", path, escape_pointer(key));
+                        match b.get(key) {
+                            Some(other) => stack.push((child, val, other)),
+                            None => diffs.push(format!("missing key {}", child)),
+                        }
+                    }
+                    for key in b.keys().filter(|key| !a.contains_key(*key)) {
+                        let child = format!("{}/{}", path, escape_pointer(key));
+                        diffs.push(format!("unexpected key {}", child));
+                    }
+                }
+                (Values::Array(a), Values::Array(b)) => {
+                    for (i, (val, other)) in a.iter().zip(b).enumerate() {
+                        stack.push((format!("{}/{}", path, i), val, other));
+                    }
+                    for i in b.len()..a.len() {
+                        diffs.push(format!("missing index {}/{}", path, i));
+                    }
+                    for i in a.len()..b.len() {
+                        diffs.push(format!("unexpected index {}/{}", path, i));
+                    }
+                }
+                (Values::String(a), Values::String(b)) if a == b => {}
+                (Values::Number(a), Values::Number(b)) if a == b => {}
+                (Values::Boolean(a), Values::Boolean(b)) if a == b => {}
+                (Values::Null, Values::Null) => {}
+                _ => diffs.push(format!(
+                    "at {}: expected {}, found {}",
+                    if path.is_empty() { "/" } else { path.as_str() },
+                    expected.describe(),
+                    found.describe()
+                )),
+            }
+        }
+        diffs
+    }
+    fn describe(&self) -> String {
+        match self {
+            Values::String(_) => format!("String({})", self),
+            Values::Number(_) => format!("Number({})", self),
+            Values::Boolean(_) => format!("Boolean({})", self),
+            Values::Null => String::from("Null"),
+            Values::Struct(_) => String::from("Struct"),
+            Values::Array(_) => String::from("Array"),
+        }
+    }
+    /// serializes this [`Values`] Object into its canonical form
+    /// as described in [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785).
+    /// Keys of a [`Struct`] are sorted by their UTF-16 code units, numbers are written
+    /// in their shortest form and strings only escape the characters that need to be escaped.
+    /// Non-finite numbers can't be represented and are written as `null`
+    ///
+    /// ```
+    /// use wjp::{map, Serialize, Values};
+    /// let val = Values::Struct(map!(("b", &4.50), ("a", &1e30)));
+    /// assert_eq!(val.to_canonical_string(), "{\"a\":1e+30,\"b\":4.5}");
+    /// ```
+    ///
+    /// [`Struct`]: Values::Struct
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        self.write_canonical(&mut out);
+        out
+    }
+    fn write_canonical(&self, out: &mut String) {
+        match self {
+            Values::String(string) => write_canonical_string(string, out),
+            Values::Number(number) => write_canonical_number(*number, out),
+            Values::Struct(r#struct) => {
+                let mut entries: Vec<(&String, &Values)> = r#struct.iter().collect();
+                entries.sort_unstable_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+                out.push('{');
+                for (i, (key, val)) in entries.into_iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    write_canonical_string(key, out);
+                    out.push(':');
+                    val.write_canonical(out);
+                }
+                out.push('}');
+            }
+            Values::Array(arr) => {
+                out.push('[');
+                for (i, item) in arr.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    item.write_canonical(out);
+                }
+                out.push(']');
+            }
+            Values::Null => out.push_str(Self::NULL),
+            Values::Boolean(bool) => out.push_str(if *bool { "true" } else { "false" }),
+        }
+    }
+    // generic over the output, so Display and Values::json share the exact same formatting
+    fn write_json<W: Write>(&self, f: &mut W, extended: bool) -> std::fmt::Result {
+        match self {
+            Values::String(string) => write_string(string, f),
+            Values::Number(number) if number.is_finite() => write!(f, "{}", number),
+            Values::Number(_) if !extended => f.write_str(Self::NULL),
+            Values::Number(number) if number.is_nan() => f.write_str("NaN"),
+            Values::Number(number) if number.is_sign_negative() => f.write_str("-Infinity"),
+            Values::Number(_) => f.write_str("Infinity"),
+            Values::Struct(r#struct) => {
+                f.write_char('{')?;
+                let mut first = true;
+                for (key, val) in r#struct {
+                    if first {
+                        first = false;
+                    } else {
+                        f.write_char(',')?;
+                    }
+                    write_string(key, f)?;
+                    f.write_char(':')?;
+                    val.write_json(f, extended)?;
+                }
+                f.write_char('}')
+            }
+            Values::Array(arr) => {
+                f.write_char('[')?;
+                let mut first = true;
+                for item in arr {
+                    if first {
+                        first = false;
+                    } else {
+                        f.write_char(',')?;
+                    }
+                    item.write_json(f, extended)?;
+                }
+                f.write_char(']')
+            }
+            Values::Null => f.write_str(Self::NULL),
+            Values::Boolean(bool) => f.write_str(if *bool { "true" } else { "false" }),
+        }
+    }
+}
+
+/// Decides how [`Values::merge_with`] combines two Arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ArrayMerge {
+    /// The other Array replaces the Array
+    #[default]
+    Replace,
+    /// The items of the other Array are appended to the Array
+    Concat,
+    /// Items at the same index are merged recursively
+    /// and the remaining items of the longer Array are kept
+    ByIndex,
+}
+
+/// Decides how [`Values::redact_with`] redacts the entries with a sensitive key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Redaction {
+    /// The value of the entry is replaced by the String [`Values::REDACTED`]
+    #[default]
+    Replace,
+    /// The entry is removed from the Struct
+    Remove,
+}
+
+/// Visitor that gets called by [`Values::walk`] for every value inside of a JSON hierarchy.
+/// The `path` of every value is written like a JSON Pointer, e.g. `/tags/0`,
+/// and every method does nothing by default
+pub trait ValueVisitor {
+    /// called for a Struct or an Array before any of its items are visited
+    fn enter(&mut self, _path: &str, _value: &Values) {}
+    /// called for a Struct or an Array after all of its items were visited
+    fn leave(&mut self, _path: &str, _value: &Values) {}
+    /// called for every [`String`], [`Number`], [`Boolean`] and [`Null`]
+    ///
+    /// [`String`]: Values::String
+    /// [`Number`]: Values::Number
+    /// [`Boolean`]: Values::Boolean
+    /// [`Null`]: Values::Null
+    fn scalar(&mut self, _path: &str, _value: &Values) {}
+}
+
+enum Visit<'a> {
+    Value(String, &'a Values),
+    Leave(String, &'a Values),
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn write_canonical_string(string: &str, out: &mut String) {
+    // writing into a String never fails
+    let _ = write_string(string, out);
+}
+
+// writes the String quoted, escaping `"`, `\` and every control character
+fn write_string<W: Write>(string: &str, out: &mut W) -> std::fmt::Result {
+    out.write_char('"')?;
+    let mut start = 0;
+    for (i, ch) in string.char_indices() {
+        if ch >= ' ' && ch != '"' && ch != '\\' {
+            continue;
+        }
+        out.write_str(&string[start..i])?;
+        match ch {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\u{8}' => out.write_str("\\b")?,
+            '\u{c}' => out.write_str("\\f")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            ch => write!(out, "\\u{:04x}", ch as u32)?,
+        }
+        start = i + ch.len_utf8();
+    }
+    out.write_str(&string[start..])?;
+    out.write_char('"')
+}
+
+// Numbers are written like the ECMAScript Number.prototype.toString() would write them
+fn write_canonical_number(number: f64, out: &mut String) {
+    if !number.is_finite() {
+        out.push_str(Values::NULL);
+        return;
+    }
+    if number == 0.0 {
+        out.push('0');
+        return;
+    }
+    if number.is_sign_negative() {
+        out.push('-');
+    }
+    // the shortest digits that round trip to the same number
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = i32::from_str(exponent).unwrap_or_default() + 1;
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat(-n as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push_str(if n > 0 { "e+" } else { "e-" });
+        out.push_str((n - 1).abs().to_string().as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    use crate::error::ParseError;
+    use crate::helper::SerializeHelper;
+    use crate::serializer::Serialize;
+    use crate::values::{minify, prettify, ArrayMerge, Redaction, ValueVisitor, Values};
+    use crate::{map, FALSE, NULL, TRUE};
+
+    #[test]
+    pub fn display_on_bool_true() {
+        let bool = TRUE;
+        assert_eq!(bool.to_string(), "true")
+    }
+
+    #[test]
     pub fn display_on_bool_false() {
         let bool = FALSE;
         assert_eq!(bool.to_string(), "false")
     }
 
     #[test]
-    pub fn display_on_null() {
-        let null = NULL;
-        assert_eq!(null.to_string(), "null")
+    pub fn display_on_null() {
+        let null = NULL;
+        assert_eq!(null.to_string(), "null")
+    }
+
+    #[test]
+    pub fn display_on_arr() {
+        let arr = vec![Values::Null, Values::Null];
+        let null = Values::Array(arr);
+        assert_eq!(null.to_string(), "[null,null]")
+    }
+
+    #[test]
+    pub fn display_on_num() {
+        let num = Values::Number(1.56);
+        assert_eq!(num.to_string(), "1.56")
+    }
+
+    #[test]
+    pub fn display_on_string() {
+        let num = Values::String(String::from("TEST TEST TEST"));
+        assert_eq!(num.to_string(), "\"TEST TEST TEST\"")
+    }
+
+    #[test]
+    pub fn display_on_struct() {
+        struct Hello {
+            hello: String,
+        }
+        impl Serialize for Hello {
+            fn serialize(&self) -> Values {
+                Values::Struct(map!(("hello", &self.hello)))
+            }
+        }
+        let struc = Hello {
+            hello: String::from("Moin"),
+        }
+        .serialize();
+        assert_eq!(struc.to_string(), "{\"hello\":\"Moin\"}");
+    }
+
+    #[test]
+    pub fn display_on_arr_with_struct() {
+        struct Hello {
+            hello: String,
+        }
+        impl Serialize for Hello {
+            fn serialize(&self) -> Values {
+                Values::Struct(map!(("hello", &self.hello)))
+            }
+        }
+        let arr = vec![
+            Hello {
+                hello: String::from("Moin"),
+            },
+            Hello {
+                hello: String::from("IDK"),
+            },
+            Hello {
+                hello: String::from("Hel\"lo"),
+            },
+        ];
+        assert_eq!(
+            arr.serialize().to_string(),
+            "[{\"hello\":\"Moin\"},{\"hello\":\"IDK\"},{\"hello\":\"Hel\\\"lo\"}]"
+        );
+    }
+
+    #[test]
+    pub fn as_f64_on_number() {
+        assert_eq!(Values::Number(1.5).as_f64(), Some(1.5))
+    }
+
+    #[test]
+    pub fn as_f64_on_numeric_string() {
+        assert_eq!(
+            Values::String(String::from("-12.25")).as_f64(),
+            Some(-12.25)
+        )
+    }
+
+    #[test]
+    pub fn as_f64_on_other_values() {
+        assert_eq!(Values::String(String::from("twelve")).as_f64(), None);
+        assert_eq!(TRUE.as_f64(), None);
+        assert_eq!(NULL.as_f64(), None)
+    }
+
+    #[test]
+    pub fn as_array_of_len_matching() {
+        let arr = Values::Array(vec![NULL, TRUE]);
+        assert_eq!(arr.as_array_of_len(2), Ok(&[NULL, TRUE][..]))
+    }
+
+    #[test]
+    pub fn as_array_of_len_mismatch() {
+        let arr = Values::Array(vec![NULL, TRUE]);
+        assert_eq!(
+            format!("{:?}", arr.as_array_of_len(3).unwrap_err()),
+            "expected an array of length 3 but found 2"
+        );
+        assert_eq!(
+            format!("{:?}", NULL.as_array_of_len(0).unwrap_err()),
+            "expected an array of length 0 but found null"
+        )
+    }
+
+    #[test]
+    pub fn display_on_non_finite_num() {
+        assert_eq!(Values::Number(f64::NAN).to_string(), "null");
+        assert_eq!(Values::Number(f64::INFINITY).to_string(), "null");
+        assert_eq!(Values::Number(f64::NEG_INFINITY).to_string(), "null")
+    }
+
+    #[test]
+    pub fn extended_display_on_non_finite_num() {
+        let arr = Values::Array(vec![
+            Values::Number(f64::NAN),
+            Values::Number(f64::INFINITY),
+            Values::Number(f64::NEG_INFINITY),
+            Values::Number(1.5),
+        ]);
+        assert_eq!(arr.to_extended_string(), "[NaN,Infinity,-Infinity,1.5]")
+    }
+
+    #[test]
+    pub fn entry_inserts_only_absent_keys() {
+        let mut struc = Values::Struct(map!(("present", &1)));
+        struc.entry("present").unwrap().or_insert(NULL);
+        struc.entry("absent").unwrap().or_insert(TRUE);
+        assert_eq!(
+            struc,
+            Values::Struct(map!(("present", &1), ("absent", &TRUE)))
+        )
+    }
+
+    #[test]
+    pub fn entry_on_non_struct() {
+        assert!(Values::Array(vec![]).entry("key").is_none())
+    }
+
+    #[test]
+    pub fn sorted_entries_on_struct() {
+        let struc = Values::Struct(map!(("b", &1), ("c", &2), ("a", &3), ("B", &4)));
+        let keys: Vec<&str> = struc
+            .sorted_entries()
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["B", "a", "b", "c"])
+    }
+
+    #[test]
+    pub fn sorted_entries_on_non_struct() {
+        assert_eq!(NULL.sorted_entries(), None)
+    }
+
+    #[test]
+    pub fn get_path_nested() {
+        let users = vec![
+            Values::Struct(map!(("name", "Merlin"))),
+            Values::Struct(map!(("name", "Morgana"), ("tags", &vec!["a", "b"]))),
+        ];
+        let val = Values::Struct(map!(("users", &users)));
+        assert_eq!(
+            val.get_path("users.1.name"),
+            Some(&Values::String(String::from("Morgana")))
+        );
+        assert_eq!(
+            val.get_path("users.1.tags.0"),
+            Some(&Values::String(String::from("a")))
+        );
+        assert_eq!(val.get_path(""), Some(&val));
     }
 
     #[test]
-    pub fn display_on_arr() {
-        let arr = vec![Values::Null, Values::Null];
-        let null = Values::Array(arr);
-        assert_eq!(null.to_string(), "[null,null]")
+    pub fn get_path_missing() {
+        let val = Values::Struct(map!(("users", &vec![NULL])));
+        assert_eq!(val.get_path("users.1"), None);
+        assert_eq!(val.get_path("users.first"), None);
+        assert_eq!(val.get_path("users.0.name"), None);
+        assert_eq!(val.get_path("groups"), None)
     }
 
     #[test]
-    pub fn display_on_num() {
-        let num = Values::Number(1.56);
-        assert_eq!(num.to_string(), "1.56")
+    pub fn canonical_numbers() {
+        let cases = [
+            ("333333333.33333329".parse().unwrap(), "333333333.3333333"),
+            (1e30, "1e+30"),
+            (4.50, "4.5"),
+            (2e-3, "0.002"),
+            (0.000000000000000000000000001, "1e-27"),
+            (-0.0, "0"),
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (-1.5e-7, "-1.5e-7"),
+            (0.000001, "0.000001"),
+            (123.0, "123"),
+            (f64::NAN, "null"),
+        ];
+        for (num, expected) in cases {
+            assert_eq!(Values::Number(num).to_canonical_string(), expected)
+        }
     }
 
     #[test]
-    pub fn display_on_string() {
-        let num = Values::String(String::from("TEST TEST TEST"));
-        assert_eq!(num.to_string(), "\"TEST TEST TEST\"")
+    pub fn canonical_rfc_example() {
+        let val = Values::Struct(map!(
+            (
+                "numbers",
+                &vec![
+                    "333333333.33333329".parse::<f64>().unwrap(),
+                    1E30,
+                    4.50,
+                    2e-3,
+                    0.000000000000000000000000001
+                ]
+            ),
+            ("string", "\u{20ac}$\u{f}\nA'B\"\\\\\"/"),
+            ("literals", &vec![NULL, TRUE, FALSE])
+        ));
+        assert_eq!(
+            val.to_canonical_string(),
+            "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"€$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}"
+        )
     }
 
     #[test]
-    pub fn display_on_struct() {
-        struct Hello {
-            hello: String,
+    pub fn canonical_key_order() {
+        let val = Values::Struct(map!(
+            ("\u{20ac}", "Euro Sign"),
+            ("\r", "Carriage Return"),
+            ("\u{fb33}", "Hebrew Letter Dalet With Dagesh"),
+            ("1", "One"),
+            ("\u{1f600}", "Emoji: Grinning Face"),
+            ("\u{80}", "Control"),
+            ("\u{f6}", "Latin Small Letter O With Diaeresis")
+        ));
+        let canonical = val.to_canonical_string();
+        let positions: Vec<usize> = [
+            "Carriage Return",
+            "One",
+            "Control",
+            "Latin Small",
+            "Euro Sign",
+            "Emoji",
+            "Hebrew",
+        ]
+        .iter()
+        .map(|name| canonical.find(name).unwrap())
+        .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]))
+    }
+
+    #[test]
+    pub fn describe_diff_on_equal_values() {
+        let val = Values::Struct(map!(("a", &vec![1, 2]), ("b", &NULL)));
+        assert!(val.describe_diff(&val.clone()).is_empty())
+    }
+
+    #[test]
+    pub fn describe_diff_on_nested_values() {
+        let expected = Values::Struct(map!(
+            ("a", &Values::Struct(map!(("b", &1), ("c", &true)))),
+            ("list", &vec![1, 2])
+        ));
+        let found = Values::Struct(map!(
+            ("a", &Values::Struct(map!(("b", "x"), ("d/e", &NULL)))),
+            ("list", &vec![1])
+        ));
+        let mut diff = expected.describe_diff(&found);
+        diff.sort();
+        assert_eq!(
+            diff,
+            vec![
+                "at /a/b: expected Number(1), found String(\"x\")",
+                "missing index /list/1",
+                "missing key /a/c",
+                "unexpected key /a/d~1e",
+            ]
+        )
+    }
+
+    #[test]
+    pub fn describe_diff_on_root() {
+        assert_eq!(
+            TRUE.describe_diff(&Values::Array(vec![])),
+            vec!["at /: expected Boolean(true), found Array"]
+        )
+    }
+
+    #[test]
+    pub fn from_str_on_struct() {
+        let val: Values = "{\"a\": [1, \"b\"], \"c\": false}".parse().unwrap();
+        assert_eq!(
+            val,
+            Values::Struct(map!(
+                (
+                    "a",
+                    &vec![Values::Number(1.0), Values::String(String::from("b"))]
+                ),
+                ("c", &FALSE)
+            ))
+        )
+    }
+
+    #[test]
+    pub fn from_str_on_invalid_json() {
+        assert!("{\"a\":}".parse::<Values>().is_err())
+    }
+
+    #[test]
+    pub fn map_leaves_uppercases_strings() {
+        let mut val = Values::Struct(map!(
+            ("name", "merlin"),
+            ("tags", &vec!["wizard", "old"]),
+            ("age", &760)
+        ));
+        val.map_leaves(|leaf| {
+            if let Values::String(string) = leaf {
+                *string = string.to_uppercase()
+            }
+        });
+        assert_eq!(
+            val,
+            Values::Struct(map!(
+                ("name", "MERLIN"),
+                ("tags", &vec!["WIZARD", "OLD"]),
+                ("age", &760)
+            ))
+        )
+    }
+
+    #[test]
+    pub fn map_leaves_on_deep_tree() {
+        let mut val = NULL;
+        for _ in 0..5_000 {
+            val = Values::Array(vec![val]);
         }
-        impl Serialize for Hello {
-            fn serialize(&self) -> Values {
-                Values::Struct(map!(("hello", &self.hello)))
+        let mut count = 0;
+        val.map_leaves(|_| count += 1);
+        assert_eq!(count, 1)
+    }
+
+    #[test]
+    pub fn get_or_with_default() {
+        let config = Values::Struct(map!(("debug", &TRUE), ("retries", &5)));
+        assert_eq!(config.get_or("debug", &FALSE), &TRUE);
+        assert_eq!(config.get_or("verbose", &FALSE), &FALSE);
+        assert_eq!(NULL.get_or("debug", &FALSE), &FALSE);
+        assert_eq!(config.get_number_or("retries", 3.0), 5.0);
+        assert_eq!(config.get_number_or("timeout", 30.0), 30.0);
+        assert_eq!(config.get_number_or("debug", 1.0), 1.0)
+    }
+
+    #[test]
+    pub fn is_truthy_on_falsy_values() {
+        for val in [
+            FALSE,
+            NULL,
+            Values::Number(0.0),
+            Values::Number(-0.0),
+            Values::Number(f64::NAN),
+            Values::String(String::new()),
+        ] {
+            assert!(!val.is_truthy(), "{:?}", val)
+        }
+    }
+
+    #[test]
+    pub fn is_truthy_on_truthy_values() {
+        for val in [
+            TRUE,
+            Values::Number(-1.0),
+            Values::String(String::from("0")),
+            Values::String(String::from("false")),
+            Values::Array(vec![]),
+            Values::Struct(map!()),
+        ] {
+            assert!(val.is_truthy(), "{:?}", val)
+        }
+    }
+
+    #[test]
+    pub fn to_string_value_coerces_scalars() {
+        assert_eq!(
+            Values::Number(42.0).to_string_value(),
+            Ok(String::from("42"))
+        );
+        assert_eq!(
+            Values::Number(-1.5).to_string_value(),
+            Ok(String::from("-1.5"))
+        );
+        assert_eq!(TRUE.to_string_value(), Ok(String::from("true")));
+        assert_eq!(
+            Values::String(String::from("text")).to_string_value(),
+            Ok(String::from("text"))
+        )
+    }
+
+    #[test]
+    pub fn to_string_value_rejects_other_values() {
+        assert!(NULL.to_string_value().is_err());
+        assert!(Values::Array(vec![]).to_string_value().is_err());
+        assert!(Values::Struct(map!()).to_string_value().is_err())
+    }
+
+    #[test]
+    pub fn take_key_moves_subtree() {
+        let inner = Values::Struct(map!(("id", &7)));
+        let mut val = Values::Struct(map!(("inner", &inner), ("other", &TRUE)));
+        assert_eq!(val.take_key("inner"), Some(inner));
+        assert_eq!(val, Values::Struct(map!(("other", &TRUE))));
+        assert_eq!(val.take_key("inner"), None);
+        assert_eq!(Values::Null.take_key("inner"), None)
+    }
+
+    #[test]
+    pub fn take_index_moves_item() {
+        let mut val = Values::Array(vec![TRUE, NULL, FALSE]);
+        assert_eq!(val.take_index(1), Some(NULL));
+        assert_eq!(val, Values::Array(vec![TRUE, FALSE]));
+        assert_eq!(val.take_index(2), None)
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    pub fn get_string_decimal_is_exact() {
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+        let parsed: Values = "[\"0.1\", \"0.2\", \"12345678901234567890.123456789\", 0.3]"
+            .parse()
+            .unwrap();
+        let list = parsed.get_list();
+        let sum = list[0].get_string_decimal().unwrap() + list[1].get_string_decimal().unwrap();
+        assert_eq!(sum, Decimal::from_str("0.3").unwrap());
+        assert_eq!(
+            list[2].get_string_decimal().unwrap().to_string(),
+            "12345678901234567890.123456789"
+        );
+        assert_eq!(list[3].get_string_decimal(), None);
+        assert_eq!(TRUE.get_string_decimal(), None)
+    }
+
+    #[test]
+    pub fn get_bytes_from_array() {
+        let bytes: Values = "[0, 127, 255]".parse().unwrap();
+        assert_eq!(bytes.get_bytes(), Ok(vec![0, 127, 255]));
+        assert!(Values::Array(vec![Values::Number(256.0)])
+            .get_bytes()
+            .is_err());
+        assert!(TRUE.get_bytes().is_err_and(|err| err
+            .to_string()
+            .starts_with("expected an Array of bytes")
+            && err.to_string().ends_with(", found boolean")));
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    pub fn get_bytes_from_base64() {
+        let array: Values = "[104, 101, 108, 108, 111]".parse().unwrap();
+        let string = Values::String(String::from("aGVsbG8="));
+        assert_eq!(string.get_bytes(), array.get_bytes());
+        assert!(Values::String(String::from("not base64!"))
+            .get_bytes()
+            .is_err());
+        assert_eq!(
+            NULL.get_bytes().map_err(|err| err.to_string()),
+            Err(String::from(
+                "expected an Array of bytes or a Base64 String, found null"
+            ))
+        )
+    }
+
+    #[test]
+    pub fn prune_empty_cascades_upward() {
+        let mut val: Values = "{\"a\":{\"b\":{\"c\":[]},\"d\":null},\"e\":[{},{\"f\":{}}],\"g\":1}"
+            .parse()
+            .unwrap();
+        let mut keep_nulls = val.clone();
+        val.prune_empty(true);
+        assert_eq!(val, "{\"e\":[{},{}],\"g\":1}".parse().unwrap());
+        keep_nulls.prune_empty(false);
+        assert_eq!(keep_nulls.get_path("a.d"), Some(&NULL));
+        assert_eq!(keep_nulls.get_path("a.b"), None)
+    }
+
+    #[test]
+    pub fn as_str_borrows() {
+        let val = Values::String(String::from("wizard"));
+        let Values::String(inner) = &val else {
+            panic!("expected a string")
+        };
+        let borrowed = val.as_str().unwrap();
+        assert_eq!(borrowed, "wizard");
+        assert_eq!(borrowed.as_ptr(), inner.as_ptr());
+        assert_eq!(Values::Number(1.0).as_str(), None)
+    }
+
+    #[test]
+    pub fn take_leaves_null_behind() {
+        let mut val = Values::Struct(map!(("items", &vec![1, 2])));
+        let items = val.entry("items").unwrap().or_insert(NULL).take();
+        assert_eq!(
+            items,
+            Values::Array(vec![Values::Number(1.0), Values::Number(2.0)])
+        );
+        assert_eq!(val, Values::Struct(map!(("items", &NULL))));
+    }
+
+    fn hash_of(val: &Values) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        val.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    pub fn equal_values_hash_equally() {
+        let a: Values = "{\"a\":[1,null],\"b\":{\"c\":true}}".parse().unwrap();
+        let b: Values = "{\"b\":{\"c\":true},\"a\":[1,null]}".parse().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_eq!(Values::Number(0.0), Values::Number(-0.0));
+        assert_eq!(
+            hash_of(&Values::Number(0.0)),
+            hash_of(&Values::Number(-0.0))
+        );
+        assert_eq!(Values::Number(f64::NAN), Values::Number(-f64::NAN));
+        assert_eq!(
+            hash_of(&Values::Number(f64::NAN)),
+            hash_of(&Values::Number(-f64::NAN))
+        );
+        assert_ne!(Values::Number(1.0), Values::String(String::from("1")));
+    }
+
+    #[test]
+    pub fn values_as_map_keys() {
+        let mut counts: HashMap<Values, u32> = HashMap::new();
+        for json in ["1", "\"1\"", "1.0", "[1]", "{}", "{}"] {
+            *counts.entry(json.parse().unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts[&Values::Number(1.0)], 2);
+        assert_eq!(counts[&Values::Struct(HashMap::new())], 2);
+    }
+
+    #[test]
+    pub fn coerce_array_from_numeric_keys() {
+        let val: Values = "{\"0\":\"a\",\"1\":\"b\"}".parse().unwrap();
+        assert_eq!(
+            val.coerce_array(),
+            Some(vec![
+                Values::String(String::from("a")),
+                Values::String(String::from("b"))
+            ])
+        );
+        let gappy: Values = "{\"0\":\"a\",\"2\":\"c\"}".parse().unwrap();
+        assert_eq!(gappy.coerce_array(), None);
+        let padded: Values = "{\"00\":\"a\"}".parse().unwrap();
+        assert_eq!(padded.coerce_array(), None);
+        assert_eq!(Values::Struct(HashMap::new()).coerce_array(), Some(vec![]));
+        assert_eq!(TRUE.coerce_array(), None)
+    }
+
+    #[test]
+    pub fn coerce_bool_int_on_numbers() {
+        assert_eq!(Values::Number(1.0).coerce_bool_int(), Ok(true));
+        assert_eq!(Values::Number(0.0).coerce_bool_int(), Ok(false));
+        assert_eq!(FALSE.coerce_bool_int(), Ok(false));
+        assert!(Values::Number(2.0).coerce_bool_int().is_err());
+        assert!(Values::Number(0.5).coerce_bool_int().is_err());
+        assert!(Values::String(String::from("1")).coerce_bool_int().is_err());
+        assert_eq!(Values::Number(1.0).get_bool(), None)
+    }
+
+    #[test]
+    pub fn walk_collects_string_paths() {
+        struct StringPaths(Vec<String>);
+        impl ValueVisitor for StringPaths {
+            fn scalar(&mut self, path: &str, value: &Values) {
+                if value.is_string() {
+                    self.0.push(String::from(path))
+                }
             }
         }
-        let struc = Hello {
-            hello: String::from("Moin"),
+        let val: Values = "{\"name\":\"x\",\"tags\":[\"a\",1,\"b\"],\"a/b\":{\"c\":\"d\"}}"
+            .parse()
+            .unwrap();
+        let mut paths = StringPaths(Vec::new());
+        val.walk(&mut paths);
+        assert_eq!(paths.0, vec!["/a~1b/c", "/name", "/tags/0", "/tags/2"]);
+    }
+
+    #[test]
+    pub fn walk_enters_and_leaves_in_order() {
+        struct Events(Vec<String>);
+        impl ValueVisitor for Events {
+            fn enter(&mut self, path: &str, _value: &Values) {
+                self.0.push(format!("enter {}", path))
+            }
+            fn leave(&mut self, path: &str, _value: &Values) {
+                self.0.push(format!("leave {}", path))
+            }
         }
-        .serialize();
-        assert_eq!(struc.to_string(), "{\"hello\":\"Moin\"}");
+        let mut deep = NULL;
+        for _ in 0..5_000 {
+            deep = Values::Array(vec![deep]);
+        }
+        let mut events = Events(Vec::new());
+        deep.walk(&mut events);
+        assert_eq!(events.0.len(), 10_000);
+        assert_eq!(events.0[0], "enter ");
+        assert_eq!(events.0[1], "enter /0");
+        assert_eq!(events.0[9_999], "leave ");
     }
 
     #[test]
-    pub fn display_on_arr_with_struct() {
-        struct Hello {
-            hello: String,
+    pub fn infer_schema_on_nested_sample() {
+        let sample: Values = "{\"user\":{\"id\":1,\"admin\":false,\"email\":null},\
+            \"posts\":[{\"title\":\"a\"},{\"title\":\"b\"}],\"mixed\":[1,\"x\"],\"empty\":[]}"
+            .parse()
+            .unwrap();
+        let schema: Values =
+            "{\"user\":{\"id\":\"number\",\"admin\":\"boolean\",\"email\":\"null\"},\
+            \"posts\":[{\"title\":\"string\"}],\"mixed\":[\"mixed\"],\"empty\":[]}"
+                .parse()
+                .unwrap();
+        assert_eq!(sample.infer_schema(), schema);
+    }
+
+    #[test]
+    pub fn canonicalize_numbers_writes_equal_numbers_equally() {
+        let mut val: Values = "{\"a\":2.0,\"b\":[-0,-0.0,0e5]}".parse().unwrap();
+        val.canonicalize_numbers();
+        assert_eq!(val.get_path("a"), Some(&Values::Number(2.0)));
+        assert_eq!(val.get_path("a").unwrap().to_string(), "2");
+        assert_eq!(val.get_path("b").unwrap().to_string(), "[0,0,0]");
+    }
+
+    #[test]
+    pub fn estimated_size_of_structure() {
+        for json in [
+            "[]",
+            "{}",
+            "[null]",
+            "[[],{}]",
+            "{\"a\":{\"b\":[false,\"c\"]}}",
+        ] {
+            let val: Values = json.parse().unwrap();
+            assert_eq!(val.estimated_size(), json.len(), "{}", json);
         }
-        impl Serialize for Hello {
-            fn serialize(&self) -> Values {
-                Values::Struct(map!(("hello", &self.hello)))
+        let numbers: Values = "[1,22,333]".parse().unwrap();
+        assert_eq!(numbers.estimated_size(), 2 + 2 + 3 * 8);
+    }
+
+    fn large_document(records: usize) -> Values {
+        let record = "{\"id\":1234,\"level\":\"info\",\"message\":\"user \\\"merlin\\\" logged in\",\"tags\":[\"auth\",\"web\"],\"latency\":0.25,\"ok\":true}";
+        format!("[{}]", vec![record; records].join(","))
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn json_is_byte_identical_to_display() {
+        for val in [
+            large_document(100),
+            Values::String(String::from("a\"\\\n")),
+            Values::Array(vec![
+                Values::Number(f64::NAN),
+                Values::Number(-1e300),
+                FALSE,
+            ]),
+            NULL,
+        ] {
+            let json = val.json();
+            assert_eq!(json, val.to_string());
+            assert_eq!(json, val.serialize().json());
+        }
+    }
+
+    // cargo test --release json_benchmark -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    pub fn json_benchmark() {
+        let val = large_document(50_000);
+        let runs = 20;
+        let time = |write: &dyn Fn() -> String| {
+            let start = std::time::Instant::now();
+            for _ in 0..runs {
+                std::hint::black_box(write());
             }
+            start.elapsed() / runs
+        };
+        let display = time(&|| val.to_string());
+        let direct = time(&|| val.json());
+        let presized = time(&|| {
+            let mut json = String::with_capacity(val.estimated_size());
+            let _ = val.write_json(&mut json, false);
+            json
+        });
+        println!(
+            "Display: {:?}, json: {:?}, json pre-sized with estimated_size: {:?}",
+            display, direct, presized
+        );
+    }
+
+    #[test]
+    pub fn replace_at_existing_and_missing_paths() {
+        let mut val: Values = "{\"a.b\":{\"c\":[1,2]},\"d\":true}".parse().unwrap();
+        assert!(val.replace_at(&["a.b", "c", "1"], Values::Number(3.0)));
+        assert_eq!(val, "{\"a.b\":{\"c\":[1,3]},\"d\":true}".parse().unwrap());
+        let before = val.clone();
+        assert!(!val.replace_at(&["a.b", "x"], NULL));
+        assert!(!val.replace_at(&["a.b", "c", "2"], NULL));
+        assert!(!val.replace_at(&["d", "e"], NULL));
+        assert_eq!(val, before);
+        assert!(val.replace_at(&[], NULL));
+        assert_eq!(val, NULL)
+    }
+
+    #[test]
+    pub fn merge_with_array_strategies() {
+        let base: Values = "{\"a\":[{\"x\":1},2],\"b\":{\"c\":true}}".parse().unwrap();
+        let layer: Values = "{\"a\":[{\"y\":3}],\"b\":{\"d\":null}}".parse().unwrap();
+        let merged = |strategy| {
+            let mut val = base.clone();
+            val.merge_with(layer.clone(), strategy);
+            val
+        };
+        let b: Values = "{\"c\":true,\"d\":null}".parse().unwrap();
+        let replaced = merged(ArrayMerge::Replace);
+        assert_eq!(
+            replaced.get_path("a"),
+            Some(&"[{\"y\":3}]".parse().unwrap())
+        );
+        assert_eq!(replaced.get_path("b"), Some(&b));
+        let concatenated = merged(ArrayMerge::Concat);
+        assert_eq!(
+            concatenated.get_path("a"),
+            Some(&"[{\"x\":1},2,{\"y\":3}]".parse().unwrap())
+        );
+        let by_index = merged(ArrayMerge::ByIndex);
+        assert_eq!(
+            by_index.get_path("a"),
+            Some(&"[{\"x\":1,\"y\":3},2]".parse().unwrap())
+        );
+        let mut scalar = Values::Number(1.0);
+        scalar.merge(Values::Array(vec![]));
+        assert_eq!(scalar, Values::Array(vec![]))
+    }
+
+    #[test]
+    pub fn select_collects_emails_of_users() {
+        let val: Values = "{\"users\":[{\"email\":\"merlin@camelot\"},{\"name\":\"Arthur\"},{\"email\":\"morgana@avalon\"}]}"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            val.select("users.*.email"),
+            vec![
+                &Values::String(String::from("merlin@camelot")),
+                &Values::String(String::from("morgana@avalon"))
+            ]
+        );
+        assert_eq!(val.select("users.1.name").len(), 1);
+        assert!(val.select("groups.*").is_empty());
+    }
+
+    #[test]
+    pub fn select_wildcard_on_struct_is_sorted() {
+        let val: Values = "{\"b\":{\"id\":2},\"a\":{\"id\":1},\"c\":3}"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            val.select("*.id"),
+            vec![&Values::Number(1.0), &Values::Number(2.0)]
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Wizard {
+        name: String,
+        level: u8,
+    }
+
+    impl TryFrom<Values> for Wizard {
+        type Error = ParseError;
+        fn try_from(value: Values) -> Result<Self, Self::Error> {
+            let mut struc = value.get_struct().ok_or(ParseError::new())?;
+            Ok(Wizard {
+                name: struc.map_val("name", String::try_from)?,
+                level: struc.map_val("level", u8::try_from)?,
+            })
         }
-        let arr = vec![
-            Hello {
-                hello: String::from("Moin"),
-            },
-            Hello {
-                hello: String::from("IDK"),
-            },
-            Hello {
-                hello: String::from("Hel\"lo"),
-            },
-        ];
+    }
+
+    #[test]
+    pub fn into_type_converts_parsed_object() {
+        let val: Values = "{\"wizard\":{\"name\":\"Merlin\",\"level\":99}}"
+            .parse()
+            .unwrap();
+        let wizard = val
+            .get_path("wizard")
+            .cloned()
+            .unwrap()
+            .into_type::<Wizard>();
         assert_eq!(
-            arr.serialize().to_string(),
-            "[{\"hello\":\"Moin\"},{\"hello\":\"IDK\"},{\"hello\":\"Hel\\\"lo\"}]"
+            wizard,
+            Ok(Wizard {
+                name: String::from("Merlin"),
+                level: 99
+            })
+        );
+        assert!(Values::Null.into_type::<Wizard>().is_err());
+    }
+
+    #[test]
+    pub fn serialized_len_matches_json() {
+        for json in [
+            "null",
+            "[]",
+            "{}",
+            "-0.000125",
+            "[1e300,-7,3.25]",
+            "\"quote \\\" backslash \\\\ tab \\t control \\u0001 unicode é\"",
+            "{\"a\\nb\":{\"c\":[true,false,null]},\"d\":\"\"}",
+        ] {
+            let val: Values = json.parse().unwrap();
+            assert_eq!(val.serialized_len(), val.json().len(), "{}", json);
+            assert_eq!(val.json().parse::<Values>(), Ok(val), "{}", json);
+        }
+        assert_eq!(Values::Number(f64::NAN).serialized_len(), 4);
+        // the quote, the control character and the tab each grow when they are escaped
+        let escaped = Values::String(String::from("\"\u{1}\t"));
+        assert_eq!(escaped.serialized_len(), "\"\\\"\\u0001\\t\"".len());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    pub fn serde_json_round_trip() {
+        let serde = serde_json::json!({
+            "name": "Merlin",
+            "level": 99,
+            "mana": 12.5,
+            "spells": ["lumos", null, true],
+            "familiar": {}
+        });
+        let val = Values::from(serde.clone());
+        assert_eq!(
+            val,
+            "{\"name\":\"Merlin\",\"level\":99,\"mana\":12.5,\"spells\":[\"lumos\",null,true],\"familiar\":{}}"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(serde_json::Value::from(val), serde);
+        assert_eq!(
+            serde_json::Value::from(Values::Number(f64::NAN)),
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    pub fn apply_defaults_fills_only_missing_keys() {
+        let mut config: Values = "{\"name\":null,\"db\":{\"port\":6543},\"tags\":[1]}"
+            .parse()
+            .unwrap();
+        let defaults: Values = "{\"name\":\"wizard\",\"debug\":false,\"db\":{\"host\":\"localhost\",\"port\":5432,\"pool\":{\"size\":4}},\"tags\":[2,3]}"
+            .parse()
+            .unwrap();
+        config.apply_defaults(&defaults);
+        let filled: Values = "{\"name\":null,\"debug\":false,\"db\":{\"host\":\"localhost\",\"port\":6543,\"pool\":{\"size\":4}},\"tags\":[1]}"
+            .parse()
+            .unwrap();
+        assert_eq!(config, filled);
+        let mut scalar = Values::Number(1.0);
+        scalar.apply_defaults(&defaults);
+        assert_eq!(scalar, Values::Number(1.0));
+    }
+
+    #[test]
+    pub fn redact_on_every_level() {
+        let val: Values = "{\"token\":\"t\",\"user\":{\"name\":\"merlin\",\"password\":{\"hash\":1}},\"sessions\":[{\"token\":\"s\",\"id\":2}]}"
+            .parse()
+            .unwrap();
+        let keys = HashSet::from(["password", "token"]);
+        let replaced: Values = "{\"token\":\"[REDACTED]\",\"user\":{\"name\":\"merlin\",\"password\":\"[REDACTED]\"},\"sessions\":[{\"token\":\"[REDACTED]\",\"id\":2}]}"
+            .parse()
+            .unwrap();
+        assert_eq!(val.redact(&keys), replaced);
+        let removed: Values = "{\"user\":{\"name\":\"merlin\"},\"sessions\":[{\"id\":2}]}"
+            .parse()
+            .unwrap();
+        assert_eq!(val.redact_with(&keys, Redaction::Remove), removed);
+    }
+
+    #[test]
+    pub fn type_histogram_on_mixed_sample() {
+        let val: Values = "{\"users\":[{\"name\":\"Merlin\",\"age\":800,\"admin\":true},{\"name\":\"Arthur\",\"age\":null,\"admin\":false}],\"total\":2}"
+            .parse()
+            .unwrap();
+        let expected = HashMap::from([
+            (Values::STRUCT, 3),
+            (Values::ARRAY, 1),
+            (Values::STRING, 2),
+            (Values::NUMBER, 2),
+            (Values::BOOLEAN, 2),
+            (Values::NULL, 1),
+        ]);
+        assert_eq!(val.type_histogram(), expected);
+        assert_eq!(NULL.type_histogram(), HashMap::from([(Values::NULL, 1)]));
+    }
+
+    #[test]
+    pub fn ndjson_lenient_skips_bad_line() {
+        let lines = Values::parse_ndjson_lenient(
+            "{\"msg\":\"start\"}\r\n{\"msg\": \"broken\"\n{\"msg\":\"end\"}",
+        );
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0]
+                .as_ref()
+                .unwrap()
+                .get_path("msg")
+                .and_then(Values::as_str),
+            Some("start")
+        );
+        let err = lines[1].as_ref().unwrap_err();
+        assert!(err.is_eof());
+        assert_eq!(format!("{:?}", err), "line 2: unexpected end of input");
+        assert_eq!(
+            lines[2]
+                .as_ref()
+                .unwrap()
+                .get_path("msg")
+                .and_then(Values::as_str),
+            Some("end")
+        );
+        assert!(Values::parse_ndjson_lenient("\n \n").is_empty());
+    }
+
+    #[test]
+    pub fn integer_and_float_numbers() {
+        let val: Values = "[2,1.5,-0,1e3,1e40,null]".parse().unwrap();
+        let list = val.get_list();
+        let integers: Vec<bool> = list.iter().map(Values::is_integer).collect();
+        assert_eq!(integers, vec![true, false, true, true, true, false]);
+        let wide: Vec<Option<i128>> = list.iter().map(Values::as_i128).collect();
+        assert_eq!(wide, vec![Some(2), None, Some(0), Some(1000), None, None]);
+        assert_eq!(Values::Number(2f64.powi(100)).as_i128(), Some(1 << 100));
+        assert_eq!(Values::Number(f64::INFINITY).as_i128(), None);
+        assert_eq!(Values::Number(1.5).as_f64(), Some(1.5));
+    }
+
+    #[test]
+    pub fn strip_null_entries_of_option_map() {
+        let update = HashMap::from([
+            ("name", Some(Values::String(String::from("Merlin")))),
+            ("title", None),
+            ("tags", Some("[null,{\"a\":null}]".parse().unwrap())),
+            ("extra", Some("{\"b\":null}".parse().unwrap())),
+        ]);
+        let mut val = update.serialize();
+        assert_eq!(val.get_path("title"), Some(&NULL));
+        val.strip_null_entries();
+        assert_eq!(
+            val,
+            "{\"name\":\"Merlin\",\"tags\":[null,{}],\"extra\":{}}"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn array_element_type_of_arrays() {
+        let numbers: Values = "[1,-2,3.5]".parse().unwrap();
+        assert_eq!(numbers.array_element_type(), Some(Values::NUMBER));
+        let structs: Values = "[{\"a\":1},{}]".parse().unwrap();
+        assert_eq!(structs.array_element_type(), Some(Values::STRUCT));
+        let mixed: Values = "[1,null,3]".parse().unwrap();
+        assert_eq!(mixed.array_element_type(), None);
+        assert_eq!(Values::Array(vec![]).array_element_type(), None);
+        assert_eq!(NULL.array_element_type(), None);
+    }
+
+    #[test]
+    pub fn minify_spacious_document() {
+        let spacious = "{\n    \"name\" : \"Merlin\" ,\n    \"spells\" : [ 1 , 2 ]\n}\n";
+        let minified = minify(spacious).unwrap();
+        assert_eq!(
+            minified.len(),
+            "{\"name\":\"Merlin\",\"spells\":[1,2]}".len()
+        );
+        assert_eq!(minified.parse::<Values>(), spacious.parse::<Values>());
+        assert!(minify("{").is_err());
+        let escaped = "[ \"a\\\\b\" , { \"a\\nb\\\"\" : \"x\\\\y\\tz\\u0001\" } ]";
+        assert_eq!(
+            minify(escaped),
+            Ok(String::from(
+                "[\"a\\\\b\",{\"a\\nb\\\"\":\"x\\\\y\\tz\\u0001\"}]"
+            ))
+        );
+        assert_eq!(minify(escaped).unwrap().parse::<Values>(), escaped.parse());
+    }
+
+    #[test]
+    pub fn prettify_compact_document() {
+        assert_eq!(
+            prettify("[{\"a\":[]},{},null]", 4),
+            Ok(String::from(
+                "[\n    {\n        \"a\": []\n    },\n    {},\n    null\n]"
+            ))
+        );
+        assert_eq!(prettify("[1]", 0), Ok(String::from("[\n1\n]")));
+        let val: Values = "{\"a\":{\"b\":[1,\"x\\\"y\"]},\"c\":true}".parse().unwrap();
+        assert_eq!(val.to_pretty_string(2).parse::<Values>(), Ok(val));
+        let val: Values = "{\"a\\nb\\\"\":[\"x\\\\y\\tz\",\"\\u0001\"]}"
+            .parse()
+            .unwrap();
+        let pretty = val.to_pretty_string(2);
+        assert_eq!(
+            pretty,
+            "{\n  \"a\\nb\\\"\": [\n    \"x\\\\y\\tz\",\n    \"\\u0001\"\n  ]\n}"
         );
+        assert_eq!(pretty.parse::<Values>(), Ok(val));
     }
 }
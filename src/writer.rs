@@ -0,0 +1,74 @@
+use std::io::{Result, Write};
+
+use crate::serializer::Serialize;
+
+/// Writes a JSON Array into a writer one element at a time,
+/// so the elements don't need to be kept in memory
+///
+/// ```
+/// use wjp::ArraySerializer;
+///
+/// let mut arr = ArraySerializer::new(Vec::new());
+/// for i in 0..3 {
+///     arr.element(&i)?;
+/// }
+/// let bytes = arr.finish()?;
+/// assert_eq!(bytes, b"[0,1,2]");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ArraySerializer<W: Write> {
+    writer: W,
+    started: bool,
+}
+
+impl<W: Write> ArraySerializer<W> {
+    /// constructs a new ArraySerializer that writes into the provided writer
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            started: false,
+        }
+    }
+    /// serializes the provided element and appends it to the Array
+    pub fn element<T: Serialize + ?Sized>(&mut self, element: &T) -> Result<()> {
+        if self.started {
+            self.writer.write_all(b",")?;
+        } else {
+            self.writer.write_all(b"[")?;
+            self.started = true;
+        }
+        self.writer.write_all(element.json().as_bytes())
+    }
+    /// closes the Array and returns the underlying writer
+    pub fn finish(mut self) -> Result<W> {
+        if !self.started {
+            self.writer.write_all(b"[")?;
+        }
+        self.writer.write_all(b"]")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::writer::ArraySerializer;
+    use crate::Serialize;
+
+    #[test]
+    pub fn streamed_matches_batch() {
+        let items = vec![Some("a"), None, Some("c\"d")];
+        let mut arr = ArraySerializer::new(Vec::new());
+        for item in &items {
+            arr.element(item).unwrap();
+        }
+        let streamed = String::from_utf8(arr.finish().unwrap()).unwrap();
+        assert_eq!(streamed, items.json())
+    }
+
+    #[test]
+    pub fn streamed_empty_array() {
+        let bytes = ArraySerializer::new(Vec::new()).finish().unwrap();
+        assert_eq!(bytes, b"[]")
+    }
+}